@@ -0,0 +1,307 @@
+//! Timeline of components added to, replaced on and removed from the selected
+//! entities.
+//!
+//! Observers of the [`Add`], [`Insert`] and [`Remove`] lifecycle events record
+//! every change to a watched entity in [`ComponentTimeline`], so components
+//! that only exist for a frame still show up. Lifecycle observers only run for
+//! the components they watch, so [`track_component_lifecycles`] respawns them
+//! over every registered component whenever new components are registered.
+//! Reflected components are registered up front, so their first insertion is
+//! seen too. The observers only exist while an entity is watched, and the
+//! watched entities follow the inspector selection through
+//! [`watch_selected_entities`].
+
+use std::collections::VecDeque;
+
+use bevy::{
+    diagnostic::FrameCount,
+    ecs::{
+        component::ComponentId, entity::EntityHashSet, system::IntoObserverSystem,
+        world::DeferredWorld,
+    },
+    prelude::*,
+};
+
+use crate::{config::InspectorInternal, state::UiState};
+
+/// How a component changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentChange {
+    /// The entity did not have the component before.
+    Added,
+    /// The component was inserted over an existing value.
+    Replaced,
+    /// The component was removed, or the entity despawned.
+    Removed,
+}
+
+/// One change of a component on a watched entity.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ComponentEvent {
+    /// Frame number from [`FrameCount`] in which the change happened.
+    pub frame: u32,
+    /// The entity that changed.
+    pub entity: Entity,
+    /// The component that changed.
+    pub component: ComponentId,
+    /// Short type name of the component, e.g. `Stunned`.
+    pub name: String,
+    /// What happened to the component.
+    pub change: ComponentChange,
+}
+
+/// Resource holding the component changes of the watched entities.
+///
+/// The watched entities are replaced whenever the inspector selection changes.
+///
+/// # Example
+///
+/// ```ignore
+/// // Watch an entity without selecting it
+/// app.world_mut()
+///     .resource_mut::<ComponentTimeline>()
+///     .watch([player]);
+/// ```
+#[derive(Resource)]
+pub struct ComponentTimeline {
+    watched: EntityHashSet,
+    events: VecDeque<ComponentEvent>,
+    max_events: usize,
+    /// Inspector selection the watched entities were last synced to.
+    selection: Vec<Entity>,
+    /// Components added by the current insert, whose `Insert` is not a replacement.
+    just_added: Vec<(Entity, ComponentId)>,
+    /// Lifecycle observers currently spawned.
+    observers: Vec<Entity>,
+    /// Number of registered components the observers watch.
+    observed_components: usize,
+}
+
+impl Default for ComponentTimeline {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl ComponentTimeline {
+    /// Keep the last `max_events` changes across all watched entities.
+    pub fn new(max_events: usize) -> Self {
+        Self {
+            watched: EntityHashSet::default(),
+            events: VecDeque::new(),
+            max_events: max_events.max(1),
+            selection: Vec::new(),
+            just_added: Vec::new(),
+            observers: Vec::new(),
+            observed_components: 0,
+        }
+    }
+
+    /// Record changes of exactly these entities from now on.
+    ///
+    /// Changes already recorded are kept. If nothing was watched before,
+    /// recording starts once [`track_component_lifecycles`] next runs.
+    pub fn watch(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.watched.clear();
+        self.watched.extend(entities);
+    }
+
+    /// Whether changes of `entity` are being recorded.
+    pub fn is_watched(&self, entity: Entity) -> bool {
+        self.watched.contains(&entity)
+    }
+
+    /// Recorded changes of every entity, oldest first.
+    pub fn events(&self) -> impl DoubleEndedIterator<Item = &ComponentEvent> + ExactSizeIterator {
+        self.events.iter()
+    }
+
+    /// Recorded changes of `entity`, oldest first.
+    pub fn events_for(&self, entity: Entity) -> impl DoubleEndedIterator<Item = &ComponentEvent> {
+        self.events
+            .iter()
+            .filter(move |event| event.entity == entity)
+    }
+
+    /// Forget every recorded change.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    fn push(&mut self, event: ComponentEvent) {
+        self.events.push_back(event);
+        while self.events.len() > self.max_events {
+            self.events.pop_front();
+        }
+    }
+}
+
+/// System that keeps the lifecycle observers watching every registered
+/// component while any entity is watched, and despawns them otherwise.
+pub fn track_component_lifecycles(world: &mut World) {
+    let Some(timeline) = world.get_resource::<ComponentTimeline>() else {
+        return;
+    };
+    if timeline.watched.is_empty() {
+        if !timeline.observers.is_empty() {
+            despawn_lifecycle_observers(world);
+        }
+        return;
+    }
+    if timeline.observers.is_empty() {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let reflected: Vec<ReflectComponent> = registry
+            .read()
+            .iter()
+            .filter_map(|registration| registration.data::<ReflectComponent>().cloned())
+            .collect();
+        for component in reflected {
+            component.register_component(world);
+        }
+    }
+
+    let registered = world.components().len();
+    let timeline = world.resource::<ComponentTimeline>();
+    if !timeline.observers.is_empty() && timeline.observed_components == registered {
+        return;
+    }
+    despawn_lifecycle_observers(world);
+    let components: Vec<ComponentId> = world
+        .components()
+        .iter_registered()
+        .map(|info| info.id())
+        .collect();
+    let observers = vec![
+        spawn_lifecycle_observer(world, record_component_added, &components),
+        spawn_lifecycle_observer(world, record_component_inserted, &components),
+        spawn_lifecycle_observer(world, record_component_removed, &components),
+    ];
+    // Spawning the observers may register their own components
+    let registered = world.components().len();
+    let mut timeline = world.resource_mut::<ComponentTimeline>();
+    timeline.observers = observers;
+    timeline.observed_components = registered;
+}
+
+fn despawn_lifecycle_observers(world: &mut World) {
+    let mut timeline = world.resource_mut::<ComponentTimeline>();
+    let stale = std::mem::take(&mut timeline.observers);
+    timeline.observed_components = 0;
+    for observer in stale {
+        world.despawn(observer);
+    }
+}
+
+fn spawn_lifecycle_observer<E: EntityEvent, M>(
+    world: &mut World,
+    system: impl IntoObserverSystem<E, (), M>,
+    components: &[ComponentId],
+) -> Entity {
+    let observer = components
+        .iter()
+        .fold(Observer::new(system), |observer, id| {
+            observer.with_component(*id)
+        });
+    world.spawn((observer, InspectorInternal)).id()
+}
+
+/// System that watches the entities selected in the inspector when the
+/// selection changes.
+pub fn watch_selected_entities(
+    ui_state: Option<Res<UiState>>,
+    mut timeline: ResMut<ComponentTimeline>,
+) {
+    timeline.just_added.clear();
+    let Some(ui_state) = ui_state else {
+        return;
+    };
+    let selected = ui_state.selected_entities.as_slice();
+    if timeline.selection != selected {
+        timeline.selection = selected.to_vec();
+        timeline.watch(selected.iter().copied());
+    }
+}
+
+/// Global observer recording components added to watched entities.
+pub fn record_component_added(event: On<Add>, mut world: DeferredWorld) {
+    record_changes(
+        &mut world,
+        event.entity,
+        event.trigger().components,
+        ComponentChange::Added,
+    );
+}
+
+/// Global observer recording components inserted over existing values on
+/// watched entities.
+pub fn record_component_inserted(event: On<Insert>, mut world: DeferredWorld) {
+    record_changes(
+        &mut world,
+        event.entity,
+        event.trigger().components,
+        ComponentChange::Replaced,
+    );
+}
+
+/// Global observer recording components removed from watched entities.
+pub fn record_component_removed(event: On<Remove>, mut world: DeferredWorld) {
+    record_changes(
+        &mut world,
+        event.entity,
+        event.trigger().components,
+        ComponentChange::Removed,
+    );
+}
+
+fn record_changes(
+    world: &mut DeferredWorld,
+    entity: Entity,
+    components: &[ComponentId],
+    change: ComponentChange,
+) {
+    if !world
+        .get_resource::<ComponentTimeline>()
+        .is_some_and(|timeline| timeline.is_watched(entity))
+    {
+        return;
+    }
+
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |count| count.0);
+    let names: Vec<String> = components
+        .iter()
+        .map(|id| {
+            world.components().get_info(*id).map_or_else(
+                || format!("{id:?}"),
+                |info| info.name().shortname().to_string(),
+            )
+        })
+        .collect();
+
+    let mut timeline = world.resource_mut::<ComponentTimeline>();
+    for (component, name) in components.iter().copied().zip(names) {
+        match change {
+            // `Insert` follows `Add` for new components; only record it for overwrites
+            ComponentChange::Added => timeline.just_added.push((entity, component)),
+            ComponentChange::Replaced => {
+                if let Some(index) = timeline
+                    .just_added
+                    .iter()
+                    .position(|added| *added == (entity, component))
+                {
+                    timeline.just_added.swap_remove(index);
+                    continue;
+                }
+            }
+            ComponentChange::Removed => {}
+        }
+        timeline.push(ComponentEvent {
+            frame,
+            entity,
+            component,
+            name,
+            change,
+        });
+    }
+}
@@ -0,0 +1,177 @@
+//! Marker components and settings resources shared by every build.
+//!
+//! These are plain data, so they exist with and without the `inspector` feature
+//! and game code can insert or query them without `cfg` attributes. Without the
+//! feature no system reads them.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+/// Resource controlling whether the inspector panel is visible.
+///
+/// Toggle with the Delete key, or the key set with
+/// [`InspectorPlugin::toggle_key`](crate::InspectorPlugin::toggle_key).
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct InspectorEnabled(pub bool);
+
+impl Default for InspectorEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+/// Marker component for entities owned by the inspector itself.
+///
+/// Entities with this marker (the egui camera, preview lights, selection markers)
+/// are hidden from the Hierarchy tab and left out of the Diagnostics entity count
+/// unless "Show inspector internals" is enabled.
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct InspectorInternal;
+
+/// Marker component for the main game camera.
+///
+/// Games should add this component to their primary camera for viewport management.
+#[derive(Component)]
+pub struct InspectorMainCamera;
+
+/// Default crosshair color (green).
+const DEFAULT_CROSSHAIR_COLOR: Color = Color::srgb(0.2, 0.8, 0.2);
+
+/// Configuration for the entity selection crosshair visual.
+///
+/// # Example
+///
+/// ```ignore
+/// // Change crosshair color to red
+/// app.insert_resource(CrosshairConfig {
+///     color: Color::srgb(1.0, 0.2, 0.2),
+/// });
+/// ```
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct CrosshairConfig {
+    /// Color of the crosshair gizmo.
+    pub color: Color,
+}
+
+impl Default for CrosshairConfig {
+    fn default() -> Self {
+        Self {
+            color: DEFAULT_CROSSHAIR_COLOR,
+        }
+    }
+}
+
+/// Settings for the Diagnostics tab.
+///
+/// # Example
+///
+/// ```ignore
+/// app.insert_resource(DiagnosticsConfig {
+///     window_secs: 30.0,
+///     pinned: vec!["physics/step_time".into()],
+///     ..default()
+/// });
+/// ```
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct DiagnosticsConfig {
+    /// Seconds of history shown in the graphs and used for the statistics.
+    pub window_secs: f32,
+    /// Number of measurements kept by the frame time and entity count
+    /// diagnostics when the inspector adds them.
+    ///
+    /// Games that add
+    /// [`FrameTimeDiagnosticsPlugin`](bevy::diagnostic::FrameTimeDiagnosticsPlugin)
+    /// themselves keep their own history length, which may cover less than
+    /// `window_secs`.
+    pub history_length: usize,
+    /// Diagnostic paths shown with a full graph at the top of the tab.
+    ///
+    /// Toggled with the pin button next to each diagnostic.
+    pub pinned: Vec<String>,
+    /// Frames slower than this many milliseconds are recorded as
+    /// [`FrameSpike`](crate::FrameSpike)s.
+    pub spike_threshold_ms: f64,
+    /// Number of frame spikes kept before the oldest are dropped.
+    pub max_spikes: usize,
+    /// Directory the Export menu writes diagnostics history to.
+    pub export_dir: PathBuf,
+}
+
+impl Default for DiagnosticsConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: 10.0,
+            history_length: 2000,
+            pinned: Vec::new(),
+            spike_threshold_ms: 1000.0 / 30.0,
+            max_spikes: 100,
+            export_dir: PathBuf::from("diagnostics"),
+        }
+    }
+}
+
+impl DiagnosticsConfig {
+    /// Pin the diagnostic at `path` to the top of the Diagnostics tab, or unpin
+    /// it if it already is. Returns whether it is pinned now.
+    pub fn toggle_pinned(&mut self, path: &str) -> bool {
+        match self.pinned.iter().position(|pinned| pinned == path) {
+            Some(index) => {
+                self.pinned.remove(index);
+                false
+            }
+            None => {
+                self.pinned.push(path.to_string());
+                true
+            }
+        }
+    }
+}
+
+/// Default file the dock layout is saved to, relative to the working directory.
+pub const DEFAULT_LAYOUT_PATH: &str = "inspector_layout.ron";
+
+/// Configuration for saving and restoring the dock layout.
+///
+/// By default the layout is written to [`DEFAULT_LAYOUT_PATH`] in the process
+/// working directory on exit, which is usually the project root under
+/// `cargo run`. Point `path` elsewhere, or set it to `None` to never touch disk.
+///
+/// # Example
+///
+/// ```ignore
+/// // Keep the layout next to other editor settings
+/// app.insert_resource(LayoutPersistence {
+///     path: Some("settings/inspector_layout.ron".into()),
+/// });
+///
+/// // Or disable persistence entirely
+/// app.insert_resource(LayoutPersistence { path: None });
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct LayoutPersistence {
+    /// File the layout is read from and written to. `None` disables persistence.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for LayoutPersistence {
+    fn default() -> Self {
+        Self {
+            path: Some(PathBuf::from(DEFAULT_LAYOUT_PATH)),
+        }
+    }
+}
+
+/// Resource controlling whether the inspector's preview lighting is active.
+///
+/// Preview lighting is only applied while the inspector panel is visible.
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+pub struct PreviewLighting {
+    /// Whether preview lighting should be shown.
+    pub enabled: bool,
+}
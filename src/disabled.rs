@@ -0,0 +1,21 @@
+//! No-op stand-ins used when the `inspector` feature is turned off.
+//!
+//! The registration API, markers and config types are shared with the full
+//! build; only the pieces that depend on bevy_egui or on the inspector's
+//! systems are replaced here.
+
+use bevy::{log::BoxedLayer, prelude::*};
+
+/// Run condition for blocking game input over inspector panels.
+///
+/// There are no panels without the `inspector` feature, so this is always false.
+pub fn egui_pointer_over_area() -> bool {
+    false
+}
+
+/// Tracing layer for timing systems.
+///
+/// There is no Systems tab without the `inspector` feature, so no layer is added.
+pub fn system_timing_layer(_app: &mut App) -> Option<BoxedLayer> {
+    None
+}
@@ -0,0 +1,255 @@
+//! Export of the recorded diagnostics history to CSV or JSON.
+//!
+//! [`DiagnosticsExport::from_world`] takes a snapshot of every measurement kept
+//! in the [`DiagnosticsStore`] plus the recorded [`FrameSpike`]s, which can then
+//! be written with [`DiagnosticsExport::save`]. The Diagnostics tab's Export
+//! menu does the same through [`export_diagnostics`].
+
+use std::{
+    fmt, fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{diagnostic::DiagnosticsStore, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    config::DiagnosticsConfig,
+    spikes::{FrameSpike, FrameSpikes},
+};
+
+/// File format of a diagnostics export.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// One row per measurement: `path,time_secs,value,suffix`.
+    ///
+    /// Frame spikes are not included; use JSON for those.
+    Csv,
+    /// The whole [`DiagnosticsExport`], including frame spikes.
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for the format, without the dot.
+    pub fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// A single measurement of a diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Sample {
+    /// Seconds since the app started, in real time.
+    pub time_secs: f64,
+    /// The measured value.
+    pub value: f64,
+}
+
+/// Recorded history of one diagnostic.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticHistory {
+    /// The diagnostic's path, e.g. `frame_time` or `physics/step_time`.
+    pub path: String,
+    /// Unit suffix of the diagnostic, e.g. `ms`.
+    pub suffix: String,
+    /// Measurements still kept in the store, oldest first.
+    pub samples: Vec<Sample>,
+}
+
+/// Snapshot of the diagnostics history, ready to be written to disk.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DiagnosticsExport {
+    /// Every diagnostic in the store, sorted by path.
+    pub diagnostics: Vec<DiagnosticHistory>,
+    /// Recorded frame spikes, oldest first.
+    pub spikes: Vec<FrameSpike>,
+}
+
+/// Errors that can occur while exporting diagnostics.
+#[derive(Debug)]
+pub enum ExportError {
+    /// Writing the export file failed.
+    Io(io::Error),
+    /// The export could not be serialized to JSON.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "diagnostics export I/O failed: {err}"),
+            ExportError::Json(err) => write!(f, "failed to serialize diagnostics: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<io::Error> for ExportError {
+    fn from(err: io::Error) -> Self {
+        ExportError::Io(err)
+    }
+}
+
+impl DiagnosticsExport {
+    /// Snapshot the [`DiagnosticsStore`] and [`FrameSpikes`] in `world`.
+    ///
+    /// Missing resources give an empty export rather than an error.
+    pub fn from_world(world: &World) -> Self {
+        let store = world.get_resource::<DiagnosticsStore>();
+        // Times are relative to startup, or to the oldest measurement without `Time<Real>`
+        let origin = world
+            .get_resource::<Time<Real>>()
+            .map(Time::<Real>::startup)
+            .or_else(|| {
+                store?
+                    .iter()
+                    .filter_map(|diagnostic| diagnostic.measurements().next())
+                    .map(|m| m.time)
+                    .min()
+            });
+
+        let mut diagnostics: Vec<DiagnosticHistory> = store
+            .map(|store| {
+                store
+                    .iter()
+                    .map(|diagnostic| DiagnosticHistory {
+                        path: diagnostic.path().as_str().to_string(),
+                        suffix: diagnostic.suffix.to_string(),
+                        samples: diagnostic
+                            .measurements()
+                            .map(|m| Sample {
+                                time_secs: origin.map_or(0.0, |origin| {
+                                    m.time.saturating_duration_since(origin).as_secs_f64()
+                                }),
+                                value: m.value,
+                            })
+                            .collect(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let spikes = world
+            .get_resource::<FrameSpikes>()
+            .map(|spikes| spikes.iter().cloned().collect())
+            .unwrap_or_default();
+
+        Self {
+            diagnostics,
+            spikes,
+        }
+    }
+
+    /// Write the measurements as CSV with a `path,time_secs,value,suffix` header.
+    pub fn write_csv(&self, mut writer: impl Write) -> Result<(), ExportError> {
+        writeln!(writer, "path,time_secs,value,suffix")?;
+        for diagnostic in &self.diagnostics {
+            for sample in &diagnostic.samples {
+                writeln!(
+                    writer,
+                    "{},{},{},{}",
+                    csv_field(&diagnostic.path),
+                    sample.time_secs,
+                    sample.value,
+                    csv_field(&diagnostic.suffix)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the whole export as pretty-printed JSON.
+    pub fn write_json(&self, writer: impl Write) -> Result<(), ExportError> {
+        serde_json::to_writer_pretty(writer, self).map_err(ExportError::Json)
+    }
+
+    /// Write the export to `path`, creating parent directories as needed.
+    pub fn save(&self, path: &Path, format: ExportFormat) -> Result<(), ExportError> {
+        if let Some(parent) = path.parent()
+            && !parent.as_os_str().is_empty()
+        {
+            fs::create_dir_all(parent)?;
+        }
+        self.write_file(fs::File::create(path)?, format)
+    }
+
+    fn write_file(&self, file: fs::File, format: ExportFormat) -> Result<(), ExportError> {
+        let mut writer = io::BufWriter::new(file);
+        match format {
+            ExportFormat::Csv => self.write_csv(&mut writer)?,
+            ExportFormat::Json => self.write_json(&mut writer)?,
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Export the diagnostics history to a timestamped file in
+/// [`DiagnosticsConfig::export_dir`], returning the file's path.
+///
+/// Existing files are never overwritten; if the name is taken, a `-1`, `-2`,
+/// ... suffix is added.
+///
+/// # Example
+///
+/// ```ignore
+/// fn export_on_f9(world: &mut World) {
+///     if world.resource::<ButtonInput<KeyCode>>().just_pressed(KeyCode::F9) {
+///         match export_diagnostics(world, ExportFormat::Csv) {
+///             Ok(path) => info!("Diagnostics written to {}", path.display()),
+///             Err(err) => warn!("{err}"),
+///         }
+///     }
+/// }
+/// ```
+pub fn export_diagnostics(world: &World, format: ExportFormat) -> Result<PathBuf, ExportError> {
+    let dir = world.get_resource::<DiagnosticsConfig>().map_or_else(
+        || DiagnosticsConfig::default().export_dir,
+        |config| config.export_dir.clone(),
+    );
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis());
+
+    fs::create_dir_all(&dir)?;
+    let (path, file) = create_new_file(
+        &dir,
+        &format!("diagnostics-{timestamp}"),
+        format.extension(),
+    )?;
+    DiagnosticsExport::from_world(world).write_file(file, format)?;
+    Ok(path)
+}
+
+/// Create `{stem}.{extension}` in `dir`, numbering the stem if the file exists.
+fn create_new_file(dir: &Path, stem: &str, extension: &str) -> io::Result<(PathBuf, fs::File)> {
+    let mut counter = 0;
+    loop {
+        let name = match counter {
+            0 => format!("{stem}.{extension}"),
+            n => format!("{stem}-{n}.{extension}"),
+        };
+        let path = dir.join(name);
+        match fs::File::create_new(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => counter += 1,
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
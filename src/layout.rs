@@ -0,0 +1,176 @@
+//! Persistence of the dock layout across runs.
+//!
+//! The layout is written as RON to [`LayoutPersistence::path`] when the app exits
+//! and restored by `initialize_ui_state` on the next launch. Named presets are
+//! stored alongside it and can be built with [`LayoutBuilder`].
+
+use std::{fmt, fs, io, path::Path};
+
+use bevy::prelude::*;
+use egui_dock::{DockState, NodeIndex, Split};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::LayoutPersistence, state::UiState, tabs::Tab};
+
+/// Serialized form of the dock layout.
+#[derive(Serialize, Deserialize)]
+pub struct SavedLayout {
+    /// The dock tree, with custom tabs referenced by [`InspectorTab::id`](crate::InspectorTab::id).
+    pub dock: DockState<Tab>,
+    /// Ids of every custom tab that was registered when the layout was saved.
+    ///
+    /// Tabs registered since then are not in this list and get placed at their
+    /// preferred dock position instead of staying hidden.
+    pub known_tabs: Vec<String>,
+    /// Named layout presets, including ones saved from the Layouts menu.
+    #[serde(default)]
+    pub presets: Vec<(String, DockState<Tab>)>,
+}
+
+/// Errors that can occur while saving or loading a layout.
+#[derive(Debug)]
+pub enum LayoutError {
+    /// Reading or writing the layout file failed.
+    Io(io::Error),
+    /// The layout could not be serialized.
+    Serialize(ron::Error),
+    /// The layout file could not be parsed.
+    Deserialize(ron::error::SpannedError),
+}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::Io(err) => write!(f, "layout file I/O failed: {err}"),
+            LayoutError::Serialize(err) => write!(f, "failed to serialize layout: {err}"),
+            LayoutError::Deserialize(err) => write!(f, "failed to parse layout: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LayoutError {}
+
+impl From<io::Error> for LayoutError {
+    fn from(err: io::Error) -> Self {
+        LayoutError::Io(err)
+    }
+}
+
+/// Write a layout to `path` as pretty-printed RON.
+pub fn save_layout(layout: &SavedLayout, path: &Path) -> Result<(), LayoutError> {
+    let contents = ron::ser::to_string_pretty(layout, ron::ser::PrettyConfig::default())
+        .map_err(LayoutError::Serialize)?;
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Read a layout previously written by [`save_layout`].
+pub fn load_layout(path: &Path) -> Result<SavedLayout, LayoutError> {
+    let contents = fs::read_to_string(path)?;
+    ron::from_str(&contents).map_err(LayoutError::Deserialize)
+}
+
+/// System that saves the dock layout when the app exits.
+pub fn save_layout_on_exit(
+    mut exit: MessageReader<AppExit>,
+    persistence: Res<LayoutPersistence>,
+    ui_state: Option<Res<UiState>>,
+) {
+    if exit.read().last().is_none() {
+        return;
+    }
+    let (Some(path), Some(ui_state)) = (persistence.path.as_deref(), ui_state) else {
+        return;
+    };
+
+    if let Err(err) = save_layout(&ui_state.saved_layout(), path) {
+        warn!("Failed to save inspector layout to {}: {err}", path.display());
+    }
+}
+
+/// Small builder for dock layouts used as named presets.
+///
+/// Starts with the tabs of the central panel and carves side panels out of it.
+/// Each `size` is the fraction of the remaining space the new panel takes.
+///
+/// # Example
+///
+/// ```ignore
+/// let level_design = LayoutBuilder::new([BuiltinTab::GameView])
+///     .left(0.2, [BuiltinTab::Hierarchy])
+///     .right(0.25, [Tab::Builtin(BuiltinTab::Inspector), Tab::custom("level_stats")])
+///     .build();
+/// ui_state.register_layout_preset("Level design", level_design);
+/// ```
+pub struct LayoutBuilder {
+    state: DockState<Tab>,
+    main: NodeIndex,
+}
+
+impl LayoutBuilder {
+    /// Start a layout whose central panel holds `tabs`.
+    pub fn new(tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+        Self {
+            state: DockState::new(collect_tabs(tabs)),
+            main: NodeIndex::root(),
+        }
+    }
+
+    /// Add a panel on the left of the central panel.
+    pub fn left(self, size: f32, tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+        self.split(Split::Left, size, tabs)
+    }
+
+    /// Add a panel on the right of the central panel.
+    pub fn right(self, size: f32, tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+        self.split(Split::Right, size, tabs)
+    }
+
+    /// Add a panel above the central panel.
+    pub fn top(self, size: f32, tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+        self.split(Split::Above, size, tabs)
+    }
+
+    /// Add a panel below the central panel.
+    pub fn bottom(self, size: f32, tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Self {
+        self.split(Split::Below, size, tabs)
+    }
+
+    /// Finish building the layout.
+    pub fn build(self) -> DockState<Tab> {
+        self.state
+    }
+
+    fn split(
+        mut self,
+        split: Split,
+        size: f32,
+        tabs: impl IntoIterator<Item = impl Into<Tab>>,
+    ) -> Self {
+        let tabs = collect_tabs(tabs);
+        if tabs.is_empty() {
+            return self;
+        }
+        let size = size.clamp(0.0, 1.0);
+        // egui_dock's fraction is the share of the left/top child
+        let fraction = match split {
+            Split::Left | Split::Above => size,
+            Split::Right | Split::Below => 1.0 - size,
+        };
+        let [main, _] = self
+            .state
+            .main_surface_mut()
+            .split_tabs(self.main, split, fraction, tabs);
+        self.main = main;
+        self
+    }
+}
+
+fn collect_tabs(tabs: impl IntoIterator<Item = impl Into<Tab>>) -> Vec<Tab> {
+    tabs.into_iter().map(Into::into).collect()
+}
@@ -8,10 +8,12 @@
 //!
 //! ## Features
 //!
-//! - **Built-in tabs**: GameView, Hierarchy, Inspector, Resources, Assets, Diagnostics
+//! - **Built-in tabs**: GameView, Hierarchy, Inspector, Resources, Assets, Diagnostics, Systems, Schedules, States, Messages, Observers (opt-in), Lifecycle
 //! - **Entity picking**: Click entities in the viewport to select them
 //! - **Viewport management**: Automatic camera viewport clipping to dock area
 //! - **Tab registration**: Games can register custom tabs via [`InspectorExt`] trait
+//! - **Layout persistence**: The dock layout is saved on exit and restored on the next run
+//! - **Time control**: Pause, single-step and change the speed of the game clock
 //!
 //! ## Built-in Tabs
 //!
@@ -19,10 +21,16 @@
 //! |-----|-------------|
 //! | Game | The game viewport, clipped to not overlap with panels |
 //! | Hierarchy | Entity tree browser with search filtering |
-//! | Inspector | Entity component inspector using reflection |
+//! | Inspector | Entity component inspector using reflection, with undo and redo |
 //! | Resources | Browse all registered resources |
 //! | Assets | Browse all loaded asset handles |
-//! | Diagnostics | FPS, frame time, and entity count metrics |
+//! | Diagnostics | Smoothed FPS and frame time, percentiles, history graphs, and every registered diagnostic |
+//! | Systems | Per-system run times grouped by schedule and set (needs the `trace` feature) |
+//! | Schedules | Every schedule's sets and systems with their ordering, run conditions and data access |
+//! | States | Current value and recent transitions of each reflected `States` type, with a switcher |
+//! | Messages | Messages sent per frame for every message type, a filterable log of their payloads, and an editor to send new ones |
+//! | Observers | Opt-in log of traced event triggers, their target entities and the registered observers |
+//! | Lifecycle | Timeline of components added to, replaced on and removed from the selected entities |
 //!
 //! ## Quick Start
 //!
@@ -30,7 +38,7 @@
 //! use msg_inspector::prelude::*;
 //!
 //! // 1. Add the plugin
-//! app.add_plugins(InspectorPlugin);
+//! app.add_plugins(InspectorPlugin::default());
 //!
 //! // 2. Mark your main camera for viewport management
 //! commands.spawn((
@@ -72,6 +80,20 @@
 //! });
 //! ```
 //!
+//! ### Adding and Removing Tabs at Runtime
+//!
+//! Use [`InspectorCommandsExt`] to change the set of tabs while the app runs:
+//!
+//! ```ignore
+//! fn on_level_loaded(mut commands: Commands) {
+//!     commands.add_inspector_tab(LevelStatsTab::default());
+//! }
+//!
+//! fn on_level_unloaded(mut commands: Commands) {
+//!     commands.remove_inspector_tab("level_stats");
+//! }
+//! ```
+//!
 //! ## Blocking Game Input Over Panels
 //!
 //! Use [`egui_pointer_over_area`] to prevent game clicks when the cursor is over panels:
@@ -80,42 +102,408 @@
 //! app.add_systems(Update, my_click_system.run_if(not(egui_pointer_over_area)));
 //! ```
 //!
+//! ## Layout Persistence
+//!
+//! The dock layout (split ratios, moved and closed tabs) is saved to
+//! `inspector_layout.ron` in the process working directory when the app exits.
+//! Change the file or turn saving off with the [`LayoutPersistence`] resource:
+//!
+//! ```ignore
+//! app.insert_resource(LayoutPersistence { path: None });
+//! ```
+//!
+//! ## Window Menu
+//!
+//! The **Window** menu above the dock lists every built-in and custom tab.
+//! Open tabs are checked; unchecking closes a tab and checking reopens it.
+//! **Reset layout** restores the default arrangement.
+//!
+//! ## Layout Presets
+//!
+//! Register named arrangements on [`UiState`] and switch between them from the
+//! **Layouts** menu or with Ctrl+1 to Ctrl+9. The current arrangement can be
+//! saved as a new preset from the same menu.
+//!
+//! ```ignore
+//! app.add_systems(PostStartup, |mut ui_state: ResMut<UiState>| {
+//!     let perf = LayoutBuilder::new([BuiltinTab::Diagnostics])
+//!         .right(0.3, [BuiltinTab::GameView])
+//!         .build();
+//!     ui_state.register_layout_preset("Performance", perf);
+//! });
+//! ```
+//!
+//! ## Time Control
+//!
+//! The menu bar pauses and resumes [`Time<Virtual>`], steps a number of frames
+//! or `FixedUpdate` ticks while paused, and sets the game speed from 0.1x to
+//! 10x. While the inspector is shown, F9 pauses, F10 steps, F7 and F8 go
+//! slower and faster; the keys are ignored while egui has keyboard focus. The
+//! inspector runs on real time and stays usable while the game is paused. Keys
+//! and step size are set on [`TimeControl`], and a key set to `None` is off:
+//!
+//! ```ignore
+//! app.insert_resource(TimeControl {
+//!     step_count: 5,
+//!     step_mode: StepMode::FixedTicks,
+//!     ..default()
+//! });
+//! ```
+//!
+//! ## Preview Lighting
+//!
+//! The inspector does not add lights to the game world unless asked. Turn on
+//! **Preview lighting** in the Window menu (or start with it via
+//! [`InspectorPlugin::with_preview_lighting`]) to light an unlit 3D scene. The
+//! game's [`AmbientLight`](bevy::light::AmbientLight) is restored and the tagged
+//! [`PreviewLight`] is despawned when it is turned off or the inspector is hidden.
+//! An ambient light the game sets while preview lighting is on is left in place.
+//!
+//! ## System Timing
+//!
+//! The **Systems** tab shows how long each system takes per frame, with the
+//! mean and max over the last 120 frames. The times come from Bevy's system
+//! tracing spans, so build with this crate's `trace` feature and hand
+//! [`system_timing_layer`] to the [`LogPlugin`](bevy::log::LogPlugin):
+//!
+//! ```ignore
+//! app.add_plugins(DefaultPlugins.set(LogPlugin {
+//!     custom_layer: msg_inspector::system_timing_layer,
+//!     ..default()
+//! }));
+//! ```
+//!
+//! Exclusive systems have no span and are not listed.
+//!
+//! ## Schedule Browser
+//!
+//! The **Schedules** tab lists every schedule as a tree of its system sets and
+//! systems, as Bevy actually built them. Selecting a system shows its sets, run
+//! conditions, `before`/`after` constraints and the components and resources it
+//! reads and writes. The same data is in the [`ScheduleGraphs`] resource.
+//! Schedules added while the app runs are picked up at the end of that frame
+//! and listed from their next build.
+//!
+//! The checkbox next to each system and set turns it off for the rest of the
+//! session, by way of a run condition the inspector adds to every system. What
+//! is turned off is kept in [`DisabledSystems`] and listed in a banner at the
+//! top of the screen, even while the inspector is hidden:
+//!
+//! ```ignore
+//! app.insert_resource(DisabledSystems::from_iter(["AiSet"]));
+//! ```
+//!
+//! ## States
+//!
+//! The **States** tab lists every [`States`] type registered for reflection,
+//! with its current value and recent transitions by frame. States registered
+//! as mutable can be switched from a dropdown of their variants, which sets
+//! their `NextState`:
+//!
+//! ```ignore
+//! app.init_state::<GameState>()
+//!     .register_type_mutable_state::<GameState>();
+//! ```
+//!
+//! ## Messages
+//!
+//! The **Messages** tab counts the messages sent each frame for every
+//! `Messages<T>` in the world, and logs the payloads of the types ticked for
+//! logging. The log can be filtered, paused and cleared. Counts and payloads
+//! are read through reflection, so register the `Messages<T>` resource:
+//!
+//! ```ignore
+//! app.add_message::<DamageEvent>()
+//!     .register_type::<Messages<DamageEvent>>();
+//! ```
+//!
+//! Types registered with
+//! [`register_inspector_message`](InspectorExt::register_inspector_message)
+//! can also be built in the tab's reflection editor and sent with a button.
+//! Values can be saved as named [`MessageMacro`]s, which [`MessageSender`]
+//! writes to [`DEFAULT_MACROS_PATH`] as RON.
+//!
+//! ## Observers
+//!
+//! The opt-in **Observers** tab records each trigger of the events picked for
+//! tracing, with the frame, the target entity and the observers registered for
+//! the event when it was triggered. Clicking a target or an observer selects
+//! it.
+//!
+//! ```ignore
+//! app.add_plugins(InspectorPlugin::new().with_tab(BuiltinTab::Observers))
+//!     .trace_inspector_entity_event::<Explode>()
+//!     .trace_inspector_event::<RoundOver>();
+//! ```
+//!
+//! ## Component Lifecycle
+//!
+//! The **Lifecycle** tab lists the components added to, replaced on and removed
+//! from the selected entities, frame by frame, so components that only exist
+//! for a frame are visible too. The changes are kept in [`ComponentTimeline`].
+//!
+//! ## Undo and Redo
+//!
+//! Edits made in the Inspector tab, and entities spawned or despawned from the
+//! Hierarchy and Inspector tabs, are recorded in [`UndoHistory`]. Undo with
+//! Ctrl+Z and redo with Ctrl+Y, or pick an entry from the **Edit** menu.
+//! [`undo`] and [`redo`] do the same from code.
+//!
+//! ## Stripping the Inspector From Release Builds
+//!
+//! Everything is behind the default `inspector` cargo feature. With it turned
+//! off, [`InspectorPlugin`], [`InspectorExt`] and [`InspectorCommandsExt`] keep
+//! their signatures but do nothing, so game modules can register tabs without
+//! `cfg` attributes. Tab UI code keeps compiling against the lean core of
+//! [`egui`], which stays a dependency, while bevy_egui, egui_dock and
+//! bevy-inspector-egui are not compiled.
+//!
+//! ```toml
+//! [features]
+//! dev = ["msg_inspector/inspector"]
+//!
+//! [dependencies]
+//! msg_inspector = { version = "0.2", default-features = false }
+//! ```
+//!
+//! Code that uses [`UiState`] or other inspector internals directly still needs
+//! to be gated on the game's own feature.
+//!
 //! ## Toggle Visibility
 //!
-//! Press the **Delete** key to toggle the inspector panel visibility.
+//! Press the **Delete** key to toggle the inspector panel visibility. The key and
+//! the initial visibility can be changed on [`InspectorPlugin`]:
+//!
+//! ```ignore
+//! app.add_plugins(InspectorPlugin::new().toggle_key(KeyCode::F12).start_hidden());
+//! ```
 
+#[cfg(feature = "inspector")]
+mod component_timeline;
+mod config;
+#[cfg(not(feature = "inspector"))]
+mod disabled;
+#[cfg(feature = "inspector")]
+mod export;
+#[cfg(feature = "inspector")]
+mod layout;
+#[cfg(feature = "inspector")]
+mod lighting;
+#[cfg(feature = "inspector")]
+mod menu;
+#[cfg(feature = "inspector")]
+mod message_monitor;
+#[cfg(feature = "inspector")]
+mod message_sender;
+#[cfg(feature = "inspector")]
+mod observer_trace;
+#[cfg(feature = "inspector")]
 mod panel;
+#[cfg(feature = "inspector")]
 mod picking;
 pub mod prelude;
+#[cfg(feature = "inspector")]
+mod reflection;
+#[cfg(feature = "inspector")]
+mod schedule_graph;
+#[cfg(feature = "inspector")]
+mod spikes;
+#[cfg(feature = "inspector")]
 mod state;
+#[cfg(feature = "inspector")]
+mod state_history;
+#[cfg(feature = "inspector")]
+mod system_timing;
+#[cfg(feature = "inspector")]
+mod system_toggle;
+#[cfg(feature = "inspector")]
+#[doc(hidden)]
+pub mod testing;
+#[cfg(feature = "inspector")]
+mod time_control;
 pub mod tabs;
+#[cfg(feature = "inspector")]
+mod undo;
+#[cfg(feature = "inspector")]
 mod viewport;
 
-use bevy::{prelude::*, render::alpha::AlphaMode};
+use bevy::prelude::*;
+#[cfg(feature = "inspector")]
+use bevy::render::alpha::AlphaMode;
+#[cfg(feature = "inspector")]
 use bevy_egui::EguiPlugin;
+#[cfg(feature = "inspector")]
 use bevy_inspector_egui::DefaultInspectorConfigPlugin;
 
+pub use config::{
+    CrosshairConfig, DiagnosticsConfig, InspectorEnabled, InspectorInternal, InspectorMainCamera,
+    LayoutPersistence, PreviewLighting, DEFAULT_LAYOUT_PATH,
+};
+#[cfg(not(feature = "inspector"))]
+pub use disabled::{egui_pointer_over_area, system_timing_layer};
+pub use tabs::{BuiltinTab, DockPosition, InspectorCommandsExt, InspectorExt, InspectorTab, Tab};
+#[cfg(feature = "inspector")]
+pub use component_timeline::{
+    record_component_added, record_component_inserted, record_component_removed,
+    track_component_lifecycles, watch_selected_entities, ComponentChange, ComponentEvent,
+    ComponentTimeline,
+};
+#[cfg(feature = "inspector")]
+pub use export::{
+    export_diagnostics, DiagnosticHistory, DiagnosticsExport, ExportError, ExportFormat, Sample,
+};
+#[cfg(feature = "inspector")]
+pub use layout::{load_layout, save_layout, LayoutBuilder, LayoutError, SavedLayout};
+#[cfg(feature = "inspector")]
+pub use lighting::{sync_preview_lighting, PreviewLight};
+#[cfg(feature = "inspector")]
+pub use message_monitor::{monitor_messages, LoggedMessage, MessageMonitor, MessageStream};
+#[cfg(feature = "inspector")]
+pub use message_sender::{
+    load_macros, load_message_macros, save_macros, write_reflected_message, MacroError,
+    MessageMacro, MessageSender, ReflectSendMessage, DEFAULT_MACROS_PATH,
+};
+#[cfg(feature = "inspector")]
+pub use observer_trace::{
+    trace_entity_trigger, trace_trigger, ObserverTrace, TracedObserver, TriggerRecord,
+};
+#[cfg(feature = "inspector")]
 pub use panel::show_ui_system;
+#[cfg(feature = "inspector")]
 pub use picking::{
     auto_add_pickable_to_sprites, handle_picking_clicks, update_picked_entity_marker,
-    CrosshairConfig,
 };
-pub use state::{GameViewportRect, InspectorEnabled, InspectorSelection, UiState};
-pub use tabs::{BuiltinTab, DockPosition, InspectorExt, InspectorTab, InspectorTabRegistry, Tab};
-pub use viewport::{egui_pointer_over_area, set_camera_viewport, InspectorMainCamera};
+#[cfg(feature = "inspector")]
+pub use schedule_graph::{
+    ScheduleGraphs, ScheduleInfo, SystemAccess, SystemInfo, SystemSetInfo, watch_new_schedules,
+};
+#[cfg(feature = "inspector")]
+pub use spikes::{capture_frame_spikes, DiagnosticValue, FrameSpike, FrameSpikes};
+#[cfg(feature = "inspector")]
+pub use state::{GameViewportRect, InspectorConfig, InspectorSelection, UiState};
+#[cfg(feature = "inspector")]
+pub use state_history::{track_states, StateHistory, StateTransitionRecord, TrackedState};
+#[cfg(feature = "inspector")]
+pub use system_timing::{
+    collect_system_timings, locate_timed_systems, system_timing_layer, SystemTiming,
+    SystemTimingSink, SystemTimings,
+};
+#[cfg(feature = "inspector")]
+pub use system_toggle::{show_disabled_systems_overlay, DisabledSystems};
+#[cfg(feature = "inspector")]
+pub use tabs::{
+    apply_pending_tabs, group_diagnostics, ArchetypeStats, ComponentStats, DiagnosticStats,
+    DuplicateTabId, EntityStats, InspectorTabRegistry,
+};
+#[cfg(feature = "inspector")]
+pub use time_control::{count_fixed_steps, step_time, time_control_hotkeys, StepMode, TimeControl};
+#[cfg(feature = "inspector")]
+pub use undo::{
+    redo, restore_to, undo, EditTarget, EntitySnapshot, InspectorEdit, UndoEntry, UndoHistory,
+    ValueChange,
+};
+#[cfg(feature = "inspector")]
+pub use viewport::{egui_pointer_over_area, set_camera_viewport};
 
 // Re-export egui so consumers don't need to depend on bevy-inspector-egui directly
-pub use bevy_inspector_egui::egui;
+pub use egui;
 
 /// Main plugin for the inspector framework.
 ///
 /// Provides a dockable panel UI with built-in tabs for entity inspection,
 /// hierarchy browsing, resource/asset exploration, and performance diagnostics.
 ///
-/// Toggle visibility with the Delete key.
-pub struct InspectorPlugin;
+/// Toggle visibility with the Delete key by default. Use the builder methods to
+/// change which parts of the inspector a game gets:
+///
+/// ```ignore
+/// app.add_plugins(
+///     InspectorPlugin::new()
+///         .toggle_key(KeyCode::F12)
+///         .start_hidden()
+///         .without_tab(BuiltinTab::Assets)
+///         .without_picking()
+///         .with_preview_lighting(),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "inspector"), allow(dead_code))]
+pub struct InspectorPlugin {
+    toggle_key: KeyCode,
+    start_hidden: bool,
+    disabled_tabs: Vec<BuiltinTab>,
+    picking: bool,
+    preview_lighting: bool,
+}
+
+impl Default for InspectorPlugin {
+    fn default() -> Self {
+        Self {
+            toggle_key: KeyCode::Delete,
+            start_hidden: false,
+            // Tracing is opt-in, see `with_tab`
+            disabled_tabs: vec![BuiltinTab::Observers],
+            picking: true,
+            preview_lighting: false,
+        }
+    }
+}
+
+impl InspectorPlugin {
+    /// Create the plugin with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Key that toggles the inspector panel (default: Delete).
+    pub fn toggle_key(mut self, key: KeyCode) -> Self {
+        self.toggle_key = key;
+        self
+    }
+
+    /// Start with the inspector panel hidden until the toggle key is pressed.
+    pub fn start_hidden(mut self) -> Self {
+        self.start_hidden = true;
+        self
+    }
+
+    /// Leave a built-in tab out of the layout and the Window menu.
+    pub fn without_tab(mut self, tab: BuiltinTab) -> Self {
+        if !self.disabled_tabs.contains(&tab) {
+            self.disabled_tabs.push(tab);
+        }
+        self
+    }
+
+    /// Add a built-in tab that is left out by default, such as
+    /// [`BuiltinTab::Observers`].
+    pub fn with_tab(mut self, tab: BuiltinTab) -> Self {
+        self.disabled_tabs.retain(|disabled| *disabled != tab);
+        self
+    }
+
+    /// Disable click-to-select entity picking and the selection crosshair.
+    pub fn without_picking(mut self) -> Self {
+        self.picking = false;
+        self
+    }
+
+    /// Start with preview lighting for 3D scenes turned on.
+    ///
+    /// Preview lighting can always be toggled from the Window menu; see
+    /// [`PreviewLighting`].
+    pub fn with_preview_lighting(mut self) -> Self {
+        self.preview_lighting = true;
+        self
+    }
+}
 
 impl Plugin for InspectorPlugin {
+    /// Without the `inspector` feature the plugin adds nothing to the app.
+    #[cfg(not(feature = "inspector"))]
+    fn build(&self, _app: &mut App) {}
+
+    #[cfg(feature = "inspector")]
     fn build(&self, app: &mut App) {
         // Core plugins
         app.add_plugins(EguiPlugin::default())
@@ -123,12 +511,64 @@ impl Plugin for InspectorPlugin {
 
         // State management
         app.register_type::<InspectorEnabled>()
+            .register_type::<InspectorInternal>()
             .register_type::<picking::PickedEntityMarker>()
-            .register_type::<picking::CrosshairConfig>()
-            .init_resource::<InspectorEnabled>()
+            .register_type::<CrosshairConfig>()
+            .insert_resource(InspectorEnabled(!self.start_hidden))
+            .insert_resource(InspectorConfig {
+                toggle_key: self.toggle_key,
+                builtin_tabs: BuiltinTab::ALL
+                    .into_iter()
+                    .filter(|tab| !self.disabled_tabs.contains(tab))
+                    .collect(),
+            })
             .init_resource::<GameViewportRect>()
             .init_resource::<InspectorTabRegistry>()
-            .init_resource::<picking::CrosshairConfig>();
+            .init_resource::<LayoutPersistence>()
+            .init_resource::<CrosshairConfig>();
+
+        // Diagnostics tab settings; the diagnostics themselves are added in `finish`
+        app.register_type::<DiagnosticsConfig>()
+            .init_resource::<DiagnosticsConfig>();
+
+        // System run times are fed by `system_timing_layer`, if the game adds it
+        if !self.disabled_tabs.contains(&BuiltinTab::Systems) {
+            app.init_resource::<SystemTimings>()
+                .add_systems(First, locate_timed_systems)
+                .add_systems(Last, (collect_system_timings, locate_timed_systems).chain());
+        }
+
+        // State values are read through reflection and compared every frame
+        if !self.disabled_tabs.contains(&BuiltinTab::States) {
+            app.init_resource::<StateHistory>()
+                .add_systems(Update, track_states);
+        }
+
+        // Messages are counted while the panel is hidden too
+        if !self.disabled_tabs.contains(&BuiltinTab::Messages) {
+            app.init_resource::<MessageMonitor>()
+                .init_resource::<MessageSender>()
+                .add_systems(Startup, load_message_macros)
+                .add_systems(Last, monitor_messages);
+        }
+
+        // Lifecycle observers only record the entities selected in the inspector
+        if !self.disabled_tabs.contains(&BuiltinTab::Lifecycle) {
+            app.init_resource::<ComponentTimeline>().add_systems(
+                First,
+                (watch_selected_entities, track_component_lifecycles).chain(),
+            );
+        }
+
+        // Triggers are only recorded for event types traced with `InspectorExt`
+        if !self.disabled_tabs.contains(&BuiltinTab::Observers) {
+            app.init_resource::<ObserverTrace>();
+        }
+
+        // Edits made in the Inspector tab can be undone from the Edit menu
+        if !self.disabled_tabs.contains(&BuiltinTab::Inspector) {
+            app.init_resource::<UndoHistory>();
+        }
 
         // Initialize UiState after tab registry so built-in tabs can be set up
         app.add_systems(Startup, state::initialize_ui_state);
@@ -137,16 +577,58 @@ impl Plugin for InspectorPlugin {
         app.add_systems(Startup, panel::setup.before(state::initialize_ui_state))
             .add_systems(
                 bevy_inspector_egui::bevy_egui::EguiPrimaryContextPass,
-                show_ui_system,
+                (show_ui_system, show_disabled_systems_overlay),
             )
             .add_systems(PostUpdate, set_camera_viewport.after(show_ui_system))
             .add_systems(Update, panel::toggle_inspector)
-            .add_systems(Update, handle_picking_clicks)
-            .add_systems(Update, update_picked_entity_marker)
-            .add_systems(Update, auto_add_pickable_to_sprites);
+            .add_systems(Update, apply_pending_tabs)
+            .add_systems(Last, layout::save_layout_on_exit);
+
+        if self.picking {
+            app.add_systems(Update, handle_picking_clicks)
+                .add_systems(Update, update_picked_entity_marker)
+                .add_systems(Update, auto_add_pickable_to_sprites);
+        }
+
+        // Game clock controls; the inspector's own UI keeps running while paused
+        app.register_type::<TimeControl>()
+            .init_resource::<TimeControl>()
+            .add_systems(First, step_time.before(bevy::time::TimeSystems))
+            .add_systems(FixedLast, count_fixed_steps)
+            .add_systems(Update, time_control_hotkeys);
+
+        // Preview lighting is opt-in and undone when turned off
+        app.register_type::<PreviewLighting>()
+            .register_type::<PreviewLight>()
+            .insert_resource(PreviewLighting {
+                enabled: self.preview_lighting,
+            })
+            .add_systems(Update, sync_preview_lighting);
 
         // Type registrations for reflection
         app.register_type::<Option<Handle<Image>>>()
             .register_type::<AlphaMode>();
     }
+
+    #[cfg(feature = "inspector")]
+    fn finish(&self, app: &mut App) {
+        if !self.disabled_tabs.contains(&BuiltinTab::Diagnostics) {
+            tabs::add_missing_diagnostics_plugins(app);
+            // Spikes are captured while the panel is hidden too, so they can be
+            // looked at after the fact
+            app.init_resource::<FrameSpikes>()
+                .add_systems(PostUpdate, capture_frame_spikes);
+        }
+    }
+
+    #[cfg(feature = "inspector")]
+    fn cleanup(&self, app: &mut App) {
+        // Every schedule exists by now and none has been built yet
+        if !self.disabled_tabs.contains(&BuiltinTab::Schedules) {
+            app.init_resource::<ScheduleGraphs>()
+                .init_resource::<DisabledSystems>()
+                .add_systems(Last, watch_new_schedules);
+            ScheduleGraphs::watch(&mut app.world_mut().resource_mut::<Schedules>());
+        }
+    }
 }
@@ -0,0 +1,81 @@
+//! Optional preview lighting for inspecting unlit 3D scenes.
+//!
+//! Off by default so the inspector never changes how the game looks. When turned
+//! on (from the Window menu or [`PreviewLighting`]), the game's [`AmbientLight`]
+//! is stashed and replaced, and a tagged [`DirectionalLight`] is spawned. Both
+//! are undone when preview lighting or the inspector is turned off, unless the
+//! game has set its own ambient light in the meantime.
+
+use bevy::{light::AmbientLight, light::DirectionalLight, prelude::*};
+
+use crate::config::{InspectorEnabled, InspectorInternal, PreviewLighting};
+
+/// Marker component for lights spawned by the inspector's preview lighting.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+#[require(InspectorInternal)]
+pub struct PreviewLight;
+
+/// System that applies or removes preview lighting when it is toggled.
+pub fn sync_preview_lighting(
+    mut commands: Commands,
+    preview: Res<PreviewLighting>,
+    enabled: Res<InspectorEnabled>,
+    ambient: Option<Res<AmbientLight>>,
+    q_lights: Query<Entity, With<PreviewLight>>,
+    mut applied: Local<bool>,
+    mut stashed_ambient: Local<Option<AmbientLight>>,
+) {
+    let active = preview.enabled && enabled.0;
+    if active == *applied {
+        return;
+    }
+    *applied = active;
+
+    if active {
+        // Remember the game's ambient light so it can be restored later
+        *stashed_ambient = ambient.map(|ambient| ambient.clone());
+        commands.insert_resource(preview_ambient());
+
+        commands.spawn((
+            DirectionalLight {
+                illuminance: 2000.0,
+                ..default()
+            },
+            Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::PI / 2.0)),
+            Name::new("Inspector Preview Light"),
+            PreviewLight,
+        ));
+    } else {
+        for entity in &q_lights {
+            commands.entity(entity).despawn();
+        }
+        let stashed = stashed_ambient.take();
+        // Leave the ambient light alone if the game changed or removed it while
+        // preview lighting was on
+        if !ambient.is_some_and(|ambient| is_preview_ambient(&ambient)) {
+            return;
+        }
+        match stashed {
+            Some(ambient) => commands.insert_resource(ambient),
+            None => commands.remove_resource::<AmbientLight>(),
+        }
+    }
+}
+
+/// Ambient light used while preview lighting is on.
+fn preview_ambient() -> AmbientLight {
+    AmbientLight {
+        color: Color::WHITE,
+        brightness: 0.02,
+        ..default()
+    }
+}
+
+/// Whether `ambient` is still the one set by preview lighting.
+fn is_preview_ambient(ambient: &AmbientLight) -> bool {
+    let preview = preview_ambient();
+    ambient.color == preview.color
+        && ambient.brightness == preview.brightness
+        && ambient.affects_lightmapped_meshes == preview.affects_lightmapped_meshes
+}
@@ -0,0 +1,264 @@
+//! Menu bar shown above the dock area.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::{
+    config::PreviewLighting,
+    state::UiState,
+    system_toggle::{self, DisabledSystems},
+    tabs::Tab,
+    time_control,
+    undo::{self, UndoHistory},
+};
+
+/// Keys used for the Ctrl+<n> layout preset shortcuts.
+const PRESET_KEYS: [egui::Key; 9] = [
+    egui::Key::Num1,
+    egui::Key::Num2,
+    egui::Key::Num3,
+    egui::Key::Num4,
+    egui::Key::Num5,
+    egui::Key::Num6,
+    egui::Key::Num7,
+    egui::Key::Num8,
+    egui::Key::Num9,
+];
+
+/// Render the menu bar at the top of the screen.
+pub(crate) fn show(ctx: &egui::Context, ui_state: &mut UiState, world: &mut World) {
+    handle_preset_shortcuts(ctx, ui_state);
+    handle_undo_shortcuts(ctx, ui_state, world);
+
+    egui::TopBottomPanel::top("inspector_menu_bar").show(ctx, |ui| {
+        egui::MenuBar::new().ui(ui, |ui| {
+            if world.contains_resource::<UndoHistory>() {
+                ui.menu_button("Edit", |ui| edit_menu(ui, ui_state, world));
+            }
+            ui.menu_button("Window", |ui| window_menu(ui, ui_state, world));
+            ui.menu_button("Layouts", |ui| layouts_menu(ui, ui_state));
+            ui.separator();
+            time_control::toolbar_ui(ui, world);
+        });
+    });
+
+    // Keep turned-off systems in view so they are not forgotten
+    let disabled = world
+        .get_resource::<DisabledSystems>()
+        .filter(|disabled| !disabled.is_empty());
+    if let Some(disabled) = disabled {
+        let mut enable_all = false;
+        egui::TopBottomPanel::top("disabled_systems_banner").show(ctx, |ui| {
+            enable_all = system_toggle::banner_ui(ui, disabled);
+        });
+        if enable_all {
+            world.resource_mut::<DisabledSystems>().clear();
+        }
+    }
+}
+
+/// Apply the n-th layout preset when Ctrl+<n> is pressed.
+fn handle_preset_shortcuts(ctx: &egui::Context, ui_state: &mut UiState) {
+    let pressed = ctx.input_mut(|input| {
+        PRESET_KEYS
+            .iter()
+            .position(|key| input.consume_key(egui::Modifiers::COMMAND, *key))
+    });
+    let Some(index) = pressed else {
+        return;
+    };
+
+    let name = ui_state.layout_preset_names().nth(index).map(str::to_owned);
+    if let Some(name) = name {
+        ui_state.apply_layout_preset(&name);
+    }
+}
+
+/// Undo on Ctrl+Z and redo on Ctrl+Y or Ctrl+Shift+Z, unless a text field
+/// has focus and needs the keys itself.
+fn handle_undo_shortcuts(ctx: &egui::Context, ui_state: &mut UiState, world: &mut World) {
+    if ctx.wants_keyboard_input() || !world.contains_resource::<UndoHistory>() {
+        return;
+    }
+    let (undo, redo) = ctx.input_mut(|input| {
+        let redo = input.consume_key(
+            egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+            egui::Key::Z,
+        ) || input.consume_key(egui::Modifiers::COMMAND, egui::Key::Y);
+        (
+            input.consume_key(egui::Modifiers::COMMAND, egui::Key::Z),
+            redo,
+        )
+    });
+    if undo {
+        undo::undo(world);
+    } else if redo {
+        undo::redo(world);
+    } else {
+        return;
+    }
+    forget_despawned(ui_state, world);
+}
+
+/// Undo and redo actions, plus the history of recorded edits.
+fn edit_menu(ui: &mut egui::Ui, ui_state: &mut UiState, world: &mut World) {
+    let history = world.resource::<UndoHistory>();
+    let (can_undo, can_redo) = (history.can_undo(), history.can_redo());
+    let done: Vec<String> = history
+        .undo_entries()
+        .map(|entry| entry.label.clone())
+        .collect();
+    let undone: Vec<String> = history
+        .redo_entries()
+        .map(|entry| entry.label.clone())
+        .collect();
+
+    let shortcut = |modifiers, key| {
+        ui.ctx()
+            .format_shortcut(&egui::KeyboardShortcut::new(modifiers, key))
+    };
+    let undo_button =
+        egui::Button::new("Undo").shortcut_text(shortcut(egui::Modifiers::COMMAND, egui::Key::Z));
+    let redo_button =
+        egui::Button::new("Redo").shortcut_text(shortcut(egui::Modifiers::COMMAND, egui::Key::Y));
+    let mut restore = None;
+    if ui.add_enabled(can_undo, undo_button).clicked() {
+        restore = Some(done.len() - 1);
+    }
+    if ui.add_enabled(can_redo, redo_button).clicked() {
+        restore = Some(done.len() + 1);
+    }
+
+    ui.separator();
+    if done.is_empty() && undone.is_empty() {
+        ui.weak("No edits yet");
+    }
+    egui::ScrollArea::vertical()
+        .max_height(300.0)
+        .show(ui, |ui| {
+            // Clicking an entry undoes or redoes everything after it
+            for (index, label) in done.iter().enumerate() {
+                let current = index + 1 == done.len();
+                if ui.selectable_label(current, label).clicked() {
+                    restore = Some(index + 1);
+                }
+            }
+            for (index, label) in undone.iter().enumerate() {
+                let text = egui::RichText::new(label).weak().italics();
+                if ui.selectable_label(false, text).clicked() {
+                    restore = Some(done.len() + index + 1);
+                }
+            }
+        });
+
+    ui.separator();
+    if ui
+        .add_enabled(can_undo || can_redo, egui::Button::new("Clear history"))
+        .clicked()
+    {
+        world.resource_mut::<UndoHistory>().clear();
+        ui.close();
+    }
+
+    if let Some(remaining) = restore {
+        undo::restore_to(world, remaining);
+        forget_despawned(ui_state, world);
+    }
+}
+
+/// Drop entities that an undo or redo despawned from the selection.
+fn forget_despawned(ui_state: &mut UiState, world: &World) {
+    let despawned: Vec<Entity> = ui_state
+        .selected_entities
+        .iter()
+        .filter(|entity| world.get_entity(*entity).is_err())
+        .collect();
+    for entity in despawned {
+        ui_state.selected_entities.remove(entity);
+    }
+}
+
+/// List layout presets and offer to save the current arrangement as a new one.
+fn layouts_menu(ui: &mut egui::Ui, ui_state: &mut UiState) {
+    let names: Vec<String> = ui_state.layout_preset_names().map(str::to_owned).collect();
+    if names.is_empty() {
+        ui.weak("No presets yet");
+    }
+    for (index, name) in names.iter().enumerate() {
+        let mut button = egui::Button::new(name);
+        if let Some(key) = PRESET_KEYS.get(index) {
+            button = button.shortcut_text(ui.ctx().format_shortcut(&egui::KeyboardShortcut::new(
+                egui::Modifiers::COMMAND,
+                *key,
+            )));
+        }
+        if ui.add(button).clicked() {
+            ui_state.apply_layout_preset(name);
+            ui.close();
+        }
+    }
+
+    ui.separator();
+    ui.horizontal(|ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut ui_state.new_preset_name)
+                .hint_text("Preset name")
+                .desired_width(120.0),
+        );
+        let name = ui_state.new_preset_name.trim().to_string();
+        if ui
+            .add_enabled(!name.is_empty(), egui::Button::new("Save current"))
+            .clicked()
+        {
+            ui_state.save_layout_preset(name);
+            ui_state.new_preset_name.clear();
+            ui.close();
+        }
+    });
+}
+
+/// List every tab with a checkmark for open ones, plus layout actions.
+fn window_menu(ui: &mut egui::Ui, ui_state: &mut UiState, world: &mut World) {
+    let builtin_tabs = ui_state.builtin_tabs().to_vec();
+    for builtin in builtin_tabs {
+        tab_toggle(ui, ui_state, Tab::Builtin(builtin), builtin.title());
+    }
+
+    let custom_tabs: Vec<(&'static str, String)> = ui_state
+        .custom_tabs
+        .iter()
+        .map(|tab| (tab.id(), tab.title().to_string()))
+        .collect();
+    if !custom_tabs.is_empty() {
+        ui.separator();
+        for (id, title) in custom_tabs {
+            tab_toggle(ui, ui_state, Tab::custom(id), &title);
+        }
+    }
+
+    if let Some(mut preview) = world.get_resource_mut::<PreviewLighting>() {
+        ui.separator();
+        let mut enabled = preview.enabled;
+        if ui.checkbox(&mut enabled, "Preview lighting").changed() {
+            preview.enabled = enabled;
+        }
+    }
+
+    ui.separator();
+    if ui.button("Reset layout").clicked() {
+        ui_state.build_default_layout();
+        ui.close();
+    }
+}
+
+/// Checkbox that opens or closes a single tab.
+fn tab_toggle(ui: &mut egui::Ui, ui_state: &mut UiState, tab: Tab, title: &str) {
+    let mut open = ui_state.is_tab_open(&tab);
+    if ui.checkbox(&mut open, title).changed() {
+        if open {
+            ui_state.open_tab(tab);
+        } else {
+            ui_state.close_tab(&tab);
+        }
+    }
+}
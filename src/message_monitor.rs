@@ -0,0 +1,321 @@
+//! Per-frame counts and a payload log of every message type in the world.
+//!
+//! Every `Messages<T>` resource is listed, but counts and payloads are read
+//! through reflection, so they need the resource registered with
+//! `app.register_type::<Messages<T>>()`. [`monitor_messages`] compares each
+//! stream's running message count with the last frame's and copies the new
+//! payloads of streams being logged into [`MessageMonitor`].
+//!
+//! The count and payloads are private fields of `Messages<T>` (`message_count`,
+//! `messages_a`/`messages_b` and their `message_id`s), read by name. A test
+//! checks that Bevy still reflects them, and a warning is logged if a stream
+//! turns out not to have them.
+
+use std::{
+    any::{TypeId, type_name},
+    collections::VecDeque,
+};
+
+use bevy::{
+    diagnostic::FrameCount,
+    prelude::*,
+    reflect::{PartialReflect, ReflectRef},
+};
+
+use crate::reflection::clone_reflected;
+
+/// Number of frames of counts kept for each stream.
+const COUNT_HISTORY: usize = 120;
+
+/// Message type only used to find the type name prefix of `Messages<T>`.
+#[derive(Message)]
+struct Probe;
+
+/// One message type and how many of its messages were sent recently.
+#[derive(Clone, Debug)]
+pub struct MessageStream {
+    /// Short name of the message type, e.g. `DamageEvent`.
+    pub name: String,
+    /// Type of the `Messages<T>` resource.
+    pub type_id: TypeId,
+    /// Whether `Messages<T>` is registered for reflection. Unregistered
+    /// streams have no counts or payloads.
+    pub reflected: bool,
+    /// Whether new payloads of this stream are added to the log.
+    pub logging: bool,
+    /// Messages sent since the stream was first seen.
+    pub total: usize,
+    counts: VecDeque<usize>,
+    seen: Option<usize>,
+}
+
+impl MessageStream {
+    /// Messages sent in each recent frame, oldest first.
+    pub fn counts(&self) -> impl ExactSizeIterator<Item = usize> + '_ {
+        self.counts.iter().copied()
+    }
+
+    /// Messages sent in the latest frame.
+    pub fn last_frame(&self) -> usize {
+        self.counts.back().copied().unwrap_or_default()
+    }
+
+    /// Mean messages per frame over the recent frames.
+    pub fn mean_per_frame(&self) -> f64 {
+        if self.counts.is_empty() {
+            return 0.0;
+        }
+        self.counts.iter().sum::<usize>() as f64 / self.counts.len() as f64
+    }
+}
+
+/// A message payload copied into the log.
+pub struct LoggedMessage {
+    /// Frame number from [`FrameCount`] in which the message was seen.
+    pub frame: u32,
+    /// Name of the stream the message was sent on.
+    pub stream: String,
+    /// Running id of the message within its stream.
+    pub id: usize,
+    /// `Debug` output of the payload, used for filtering.
+    pub text: String,
+    /// Copy of the payload.
+    pub value: Box<dyn PartialReflect>,
+}
+
+/// Resource holding every message stream and the log of recent payloads.
+#[derive(Resource)]
+pub struct MessageMonitor {
+    streams: Vec<MessageStream>,
+    log: VecDeque<LoggedMessage>,
+    max_log: usize,
+    paused: bool,
+    /// Number of resources in the world when streams were last looked for.
+    scanned_resources: usize,
+}
+
+impl Default for MessageMonitor {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl MessageMonitor {
+    /// Keep the last `max_log` payloads in the log.
+    pub fn new(max_log: usize) -> Self {
+        Self {
+            streams: Vec::new(),
+            log: VecDeque::new(),
+            max_log: max_log.max(1),
+            paused: false,
+            scanned_resources: 0,
+        }
+    }
+
+    /// Every message stream seen so far, sorted by name.
+    pub fn streams(&self) -> impl Iterator<Item = &MessageStream> {
+        self.streams.iter()
+    }
+
+    /// The stream of the message type with the given short name.
+    pub fn stream(&self, name: &str) -> Option<&MessageStream> {
+        self.streams.iter().find(|stream| stream.name == name)
+    }
+
+    /// Start or stop adding payloads of the stream called `name` to the log.
+    pub fn set_logging(&mut self, name: &str, logging: bool) {
+        if let Some(stream) = self.streams.iter_mut().find(|stream| stream.name == name) {
+            stream.logging = logging;
+        }
+    }
+
+    /// Logged payloads, oldest first.
+    pub fn log(&self) -> impl DoubleEndedIterator<Item = &LoggedMessage> + ExactSizeIterator {
+        self.log.iter()
+    }
+
+    /// Forget every logged payload.
+    pub fn clear_log(&mut self) {
+        self.log.clear();
+    }
+
+    /// Whether new payloads are being kept out of the log. Counts keep updating.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop or resume adding payloads to the log.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    fn stream_mut(&mut self, type_id: TypeId, name: &str) -> &mut MessageStream {
+        let index = match self.streams.iter().position(|s| s.type_id == type_id) {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .streams
+                    .partition_point(|stream| stream.name.as_str() < name);
+                self.streams.insert(
+                    index,
+                    MessageStream {
+                        name: name.to_string(),
+                        type_id,
+                        reflected: false,
+                        logging: false,
+                        total: 0,
+                        counts: VecDeque::new(),
+                        seen: None,
+                    },
+                );
+                index
+            }
+        };
+        &mut self.streams[index]
+    }
+
+    fn push_log(&mut self, message: LoggedMessage) {
+        self.log.push_back(message);
+        while self.log.len() > self.max_log {
+            self.log.pop_front();
+        }
+    }
+}
+
+/// System that counts new messages of every stream and logs their payloads.
+///
+/// Runs in `Last`, so it sees each message once in the frame it was sent.
+/// Resources are only searched for new streams when their number changes.
+pub fn monitor_messages(world: &mut World) {
+    let resources = world.storages().resources.len();
+    if world.resource::<MessageMonitor>().scanned_resources != resources {
+        let streams = find_streams(world);
+        let mut monitor = world.resource_mut::<MessageMonitor>();
+        monitor.scanned_resources = resources;
+        for (type_id, name) in streams {
+            monitor.stream_mut(type_id, &name);
+        }
+    }
+    if world.resource::<MessageMonitor>().streams.is_empty() {
+        return;
+    }
+
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |count| count.0);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    world.resource_scope(|world, mut monitor: Mut<MessageMonitor>| {
+        let paused = monitor.paused;
+        let mut logged = Vec::new();
+        for stream in &mut monitor.streams {
+            let messages = registry
+                .get_type_data::<ReflectResource>(stream.type_id)
+                .and_then(|reflect| reflect.reflect(&*world).ok());
+            let Some(messages) = messages else {
+                continue;
+            };
+            let Some(count) = message_count(messages.as_partial_reflect()) else {
+                warn_once!(
+                    "`Messages<{}>` is reflected without a `message_count` field; \
+                     the Messages tab cannot read it with this Bevy version",
+                    stream.name
+                );
+                continue;
+            };
+            stream.reflected = true;
+
+            // A count lower than last frame's means the resource was replaced
+            let seen = stream.seen.filter(|seen| *seen <= count).unwrap_or(count);
+            stream.seen = Some(count);
+            stream.total += count - seen;
+            stream.counts.push_back(count - seen);
+            while stream.counts.len() > COUNT_HISTORY {
+                stream.counts.pop_front();
+            }
+
+            if !stream.logging || paused || count == seen {
+                continue;
+            }
+            let mut new_messages = payloads(messages.as_partial_reflect(), seen);
+            new_messages.sort_by_key(|(id, _)| *id);
+            for (id, value) in new_messages {
+                let value = clone_reflected(value);
+                logged.push(LoggedMessage {
+                    frame,
+                    stream: stream.name.clone(),
+                    id,
+                    text: format!("{value:?}"),
+                    value,
+                });
+            }
+        }
+        for message in logged {
+            monitor.push_log(message);
+        }
+    });
+}
+
+/// Every `Messages<T>` resource in the world, with the short name of `T`.
+fn find_streams(world: &World) -> Vec<(TypeId, String)> {
+    let prefix = type_name::<Messages<Probe>>()
+        .split_once('<')
+        .map_or("", |(prefix, _)| prefix);
+    world
+        .iter_resources()
+        .filter_map(|(info, _)| {
+            let name = info.name().to_string();
+            let message = name
+                .strip_prefix(prefix)?
+                .strip_prefix('<')?
+                .strip_suffix('>')?;
+            Some((info.type_id()?, ShortName(message).to_string()))
+        })
+        .collect()
+}
+
+/// Running number of messages ever written to a reflected `Messages<T>`.
+fn message_count(messages: &dyn PartialReflect) -> Option<usize> {
+    let ReflectRef::Struct(messages) = messages.reflect_ref() else {
+        return None;
+    };
+    messages
+        .field("message_count")?
+        .try_downcast_ref::<usize>()
+        .copied()
+}
+
+/// Payloads in both buffers of a reflected `Messages<T>` whose id is at least `from`.
+fn payloads(messages: &dyn PartialReflect, from: usize) -> Vec<(usize, &dyn PartialReflect)> {
+    let mut found = Vec::new();
+    let ReflectRef::Struct(messages) = messages.reflect_ref() else {
+        return found;
+    };
+    for buffer in ["messages_a", "messages_b"] {
+        let Some(ReflectRef::Struct(sequence)) = messages.field(buffer).map(|b| b.reflect_ref())
+        else {
+            continue;
+        };
+        let Some(ReflectRef::List(list)) = sequence.field("messages").map(|m| m.reflect_ref())
+        else {
+            continue;
+        };
+        for instance in list.iter() {
+            let ReflectRef::Struct(instance) = instance.reflect_ref() else {
+                continue;
+            };
+            let id = instance
+                .field("message_id")
+                .and_then(|id| match id.reflect_ref() {
+                    ReflectRef::Struct(id) => id.field("id")?.try_downcast_ref::<usize>().copied(),
+                    _ => None,
+                });
+            if let (Some(id), Some(message)) = (id, instance.field("message"))
+                && id >= from
+            {
+                found.push((id, message));
+            }
+        }
+    }
+    found
+}
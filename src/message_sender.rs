@@ -0,0 +1,347 @@
+//! Building messages with the reflection UI and writing them into the world.
+//!
+//! Message types opt in with
+//! [`register_inspector_message`](crate::InspectorExt::register_inspector_message),
+//! which adds [`ReflectSendMessage`] to their type registration. The Messages
+//! tab edits a default value of the type and writes it with
+//! [`write_reflected_message`]. Values can be kept as named [`MessageMacro`]s,
+//! which are saved as RON to [`MessageSender::path`].
+
+use std::{
+    any::TypeId,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use bevy::{
+    prelude::*,
+    reflect::{
+        FromType, PartialReflect, ReflectFromReflect, TypeRegistry,
+        serde::{ReflectDeserializer, ReflectSerializer},
+    },
+};
+use serde::de::{DeserializeSeed, SeqAccess, Visitor};
+
+use crate::reflection::clone_reflected;
+
+/// Default file message macros are saved to, relative to the working directory.
+pub const DEFAULT_MACROS_PATH: &str = "inspector_macros.ron";
+
+/// Type data for message types that can be sent from the inspector.
+///
+/// Added by [`register_inspector_message`](crate::InspectorExt::register_inspector_message).
+#[derive(Clone)]
+pub struct ReflectSendMessage {
+    default: fn() -> Box<dyn Reflect>,
+    write: fn(&mut World, &dyn PartialReflect) -> bool,
+}
+
+impl ReflectSendMessage {
+    /// A default value of the message type, to start editing from.
+    pub fn default_value(&self) -> Box<dyn Reflect> {
+        (self.default)()
+    }
+
+    /// Write `message` into the world. Returns false if it is not a value of
+    /// this message type.
+    pub fn write(&self, world: &mut World, message: &dyn PartialReflect) -> bool {
+        (self.write)(world, message)
+    }
+}
+
+impl<M: Message + Default + FromReflect> FromType<M> for ReflectSendMessage {
+    fn from_type() -> Self {
+        Self {
+            default: || Box::new(M::default()),
+            write: |world, message| match M::from_reflect(message) {
+                Some(message) => {
+                    world.write_message(message);
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+}
+
+/// Write a reflected message into the world.
+///
+/// Returns false if the message's type was not registered with
+/// [`register_inspector_message`](crate::InspectorExt::register_inspector_message).
+pub fn write_reflected_message(world: &mut World, message: &dyn PartialReflect) -> bool {
+    let Some(type_id) = message
+        .get_represented_type_info()
+        .map(|info| info.type_id())
+    else {
+        return false;
+    };
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let send = registry
+        .read()
+        .get_type_data::<ReflectSendMessage>(type_id)
+        .cloned();
+    send.is_some_and(|send| send.write(world, message))
+}
+
+/// A named message value that can be sent again with one click.
+pub struct MessageMacro {
+    /// Name shown in the Messages tab.
+    pub name: String,
+    /// The message to send.
+    pub message: Box<dyn PartialReflect>,
+}
+
+impl MessageMacro {
+    /// Short type path of the message, e.g. `DamageEvent`.
+    pub fn message_type(&self) -> &str {
+        self.message
+            .get_represented_type_info()
+            .map_or("?", |info| info.type_path_table().short_path())
+    }
+}
+
+/// Resource holding the message being edited in the Messages tab and the
+/// saved message macros.
+///
+/// # Example
+///
+/// ```ignore
+/// // Keep macros out of the working directory
+/// app.insert_resource(MessageSender::with_path(Some("dev/macros.ron".into())));
+/// ```
+#[derive(Resource)]
+pub struct MessageSender {
+    /// File macros are loaded from at startup and saved to when they change.
+    /// `None` keeps them for the session only.
+    pub path: Option<PathBuf>,
+    /// Message being edited, if a type has been picked.
+    pub(crate) draft: Option<Box<dyn PartialReflect>>,
+    /// Name typed for the next macro.
+    pub(crate) new_macro_name: String,
+    macros: Vec<MessageMacro>,
+}
+
+impl Default for MessageSender {
+    fn default() -> Self {
+        Self::with_path(Some(PathBuf::from(DEFAULT_MACROS_PATH)))
+    }
+}
+
+impl MessageSender {
+    /// Save macros to `path`, or nowhere for `None`.
+    pub fn with_path(path: Option<PathBuf>) -> Self {
+        Self {
+            path,
+            draft: None,
+            new_macro_name: String::new(),
+            macros: Vec::new(),
+        }
+    }
+
+    /// Saved macros, in the order they were added.
+    pub fn macros(&self) -> impl Iterator<Item = &MessageMacro> {
+        self.macros.iter()
+    }
+
+    /// The macro with the given name.
+    pub fn get_macro(&self, name: &str) -> Option<&MessageMacro> {
+        self.macros.iter().find(|m| m.name == name)
+    }
+
+    /// Add a macro, replacing any macro with the same name.
+    pub fn add_macro(&mut self, name: impl Into<String>, message: &dyn PartialReflect) {
+        let name = name.into();
+        let message = clone_reflected(message);
+        match self.macros.iter_mut().find(|m| m.name == name) {
+            Some(existing) => existing.message = message,
+            None => self.macros.push(MessageMacro { name, message }),
+        }
+    }
+
+    /// Remove the macro with the given name, returning it if it existed.
+    pub fn remove_macro(&mut self, name: &str) -> Option<MessageMacro> {
+        let index = self.macros.iter().position(|m| m.name == name)?;
+        Some(self.macros.remove(index))
+    }
+
+    /// Write the macros to [`path`](Self::path), if set.
+    pub fn save(&self, registry: &TypeRegistry) -> Result<(), MacroError> {
+        match &self.path {
+            Some(path) => save_macros(&self.macros, path, registry),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Errors that can occur while saving or loading message macros.
+#[derive(Debug)]
+pub enum MacroError {
+    /// Reading or writing the macros file failed.
+    Io(io::Error),
+    /// The macros could not be serialized.
+    Serialize(ron::Error),
+    /// The macros file could not be parsed, or names a type that is not registered.
+    Deserialize(ron::Error),
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MacroError::Io(err) => write!(f, "macros file I/O failed: {err}"),
+            MacroError::Serialize(err) => write!(f, "failed to serialize macros: {err}"),
+            MacroError::Deserialize(err) => write!(f, "failed to parse macros: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for MacroError {}
+
+impl From<io::Error> for MacroError {
+    fn from(err: io::Error) -> Self {
+        MacroError::Io(err)
+    }
+}
+
+/// Write macros to `path` as pretty-printed RON, each as its name and the
+/// message in Bevy's reflection format.
+pub fn save_macros(
+    macros: &[MessageMacro],
+    path: &Path,
+    registry: &TypeRegistry,
+) -> Result<(), MacroError> {
+    let entries: Vec<(&str, ReflectSerializer)> = macros
+        .iter()
+        .map(|m| {
+            (
+                m.name.as_str(),
+                ReflectSerializer::new(m.message.as_ref(), registry),
+            )
+        })
+        .collect();
+    let contents = ron::ser::to_string_pretty(&entries, ron::ser::PrettyConfig::default())
+        .map_err(MacroError::Serialize)?;
+    if let Some(parent) = path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Read macros previously written by [`save_macros`].
+pub fn load_macros(path: &Path, registry: &TypeRegistry) -> Result<Vec<MessageMacro>, MacroError> {
+    let contents = fs::read_to_string(path)?;
+    let mut deserializer =
+        ron::Deserializer::from_str(&contents).map_err(|err| MacroError::Deserialize(err.code))?;
+    MacroListSeed(registry)
+        .deserialize(&mut deserializer)
+        .map_err(MacroError::Deserialize)
+}
+
+/// Startup system that loads the macros saved in a previous run.
+pub fn load_message_macros(world: &mut World) {
+    let Some(path) = world
+        .get_resource::<MessageSender>()
+        .and_then(|sender| sender.path.clone())
+    else {
+        return;
+    };
+    if !path.exists() {
+        return;
+    }
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    match load_macros(&path, &registry.read()) {
+        Ok(macros) => world.resource_mut::<MessageSender>().macros = macros,
+        Err(err) => warn!(
+            "Failed to load message macros from {}: {err}",
+            path.display()
+        ),
+    }
+}
+
+/// Deserializes the list of `(name, message)` pairs written by [`save_macros`].
+struct MacroListSeed<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for MacroListSeed<'_> {
+    type Value = Vec<MessageMacro>;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for MacroListSeed<'_> {
+    type Value = Vec<MessageMacro>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a list of named messages")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut macros = Vec::new();
+        while let Some(entry) = seq.next_element_seed(MacroSeed(self.0))? {
+            macros.push(entry);
+        }
+        Ok(macros)
+    }
+}
+
+/// Deserializes one `(name, message)` pair.
+struct MacroSeed<'a>(&'a TypeRegistry);
+
+impl<'de> DeserializeSeed<'de> for MacroSeed<'_> {
+    type Value = MessageMacro;
+
+    fn deserialize<D: serde::Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_tuple(2, self)
+    }
+}
+
+impl<'de> Visitor<'de> for MacroSeed<'_> {
+    type Value = MessageMacro;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a name and a message")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let name: String = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let message = seq
+            .next_element_seed(ReflectDeserializer::new(self.0))?
+            .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+        // Turn the dynamic value back into the real type so the editor can use its own widgets
+        let message = message
+            .get_represented_type_info()
+            .and_then(|info| self.0.get_type_data::<ReflectFromReflect>(info.type_id()))
+            .and_then(|from_reflect| from_reflect.from_reflect(message.as_ref()))
+            .map_or(message, <dyn Reflect>::into_partial_reflect);
+        Ok(MessageMacro { name, message })
+    }
+}
+
+/// Types registered with [`ReflectSendMessage`], by short type path.
+pub(crate) fn sendable_types(registry: &TypeRegistry) -> Vec<(&'static str, TypeId)> {
+    let mut types: Vec<_> = registry
+        .iter()
+        .filter(|registration| registration.data::<ReflectSendMessage>().is_some())
+        .map(|registration| {
+            (
+                registration.type_info().type_path_table().short_path(),
+                registration.type_id(),
+            )
+        })
+        .collect();
+    types.sort_by_key(|(name, _)| *name);
+    types
+}
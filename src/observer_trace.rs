@@ -0,0 +1,166 @@
+//! Log of triggered events, their targets and the observers registered for them.
+//!
+//! Event types opt in with
+//! [`trace_inspector_event`](crate::InspectorExt::trace_inspector_event) or
+//! [`trace_inspector_entity_event`](crate::InspectorExt::trace_inspector_entity_event),
+//! which add a global observer tagged [`InspectorInternal`]. Each time the event
+//! is triggered it looks up the other observers of the event in
+//! [`World::observers`] and records them in [`ObserverTrace`].
+//!
+//! These are the observers registered when the trigger starts, not a record
+//! of which ones ran: observers spawned or despawned by an earlier observer of
+//! the same trigger are listed as they were before it.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    diagnostic::FrameCount,
+    ecs::{event::EventKey, world::DeferredWorld},
+    prelude::*,
+};
+
+use crate::config::InspectorInternal;
+
+/// An observer registered for the event of a traced trigger.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TracedObserver {
+    /// The observer entity.
+    pub entity: Entity,
+    /// The entity's [`Name`], or the short name of the observer system.
+    pub name: String,
+}
+
+/// One trigger of a traced event.
+#[derive(Clone, Debug)]
+pub struct TriggerRecord {
+    /// Frame number from [`FrameCount`] in which the event was triggered.
+    pub frame: u32,
+    /// Short type path of the event, e.g. `Explode`.
+    pub event: String,
+    /// Entity the event targeted, for entity events. Propagated events get
+    /// one record per entity they reach.
+    pub target: Option<Entity>,
+    /// Global observers and observers watching the target that were registered
+    /// when the event was triggered, sorted by entity.
+    pub observers: Vec<TracedObserver>,
+}
+
+/// Resource holding the most recent triggers of traced events.
+#[derive(Resource)]
+pub struct ObserverTrace {
+    records: VecDeque<TriggerRecord>,
+    max_records: usize,
+    paused: bool,
+}
+
+impl Default for ObserverTrace {
+    fn default() -> Self {
+        Self::new(500)
+    }
+}
+
+impl ObserverTrace {
+    /// Keep the last `max_records` triggers.
+    pub fn new(max_records: usize) -> Self {
+        Self {
+            records: VecDeque::new(),
+            max_records: max_records.max(1),
+            paused: false,
+        }
+    }
+
+    /// Recorded triggers, oldest first.
+    pub fn records(&self) -> impl DoubleEndedIterator<Item = &TriggerRecord> + ExactSizeIterator {
+        self.records.iter()
+    }
+
+    /// Forget every recorded trigger.
+    pub fn clear(&mut self) {
+        self.records.clear();
+    }
+
+    /// Whether new triggers are being ignored.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Stop or resume recording triggers.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    fn push(&mut self, record: TriggerRecord) {
+        self.records.push_back(record);
+        while self.records.len() > self.max_records {
+            self.records.pop_front();
+        }
+    }
+}
+
+/// Global observer recording each trigger of the event `E`.
+pub fn trace_trigger<E: Event>(event: On<E>, mut world: DeferredWorld) {
+    record_trigger(&mut world, event.event_key(), ShortName::of::<E>(), None);
+}
+
+/// Global observer recording each trigger of the entity event `E` with its target.
+pub fn trace_entity_trigger<E: EntityEvent>(event: On<E>, mut world: DeferredWorld) {
+    let target = event.event().event_target();
+    record_trigger(
+        &mut world,
+        event.event_key(),
+        ShortName::of::<E>(),
+        Some(target),
+    );
+}
+
+fn record_trigger(
+    world: &mut DeferredWorld,
+    event_key: EventKey,
+    event: ShortName,
+    target: Option<Entity>,
+) {
+    if world
+        .get_resource::<ObserverTrace>()
+        .is_none_or(ObserverTrace::is_paused)
+    {
+        return;
+    }
+
+    let mut entities = Vec::new();
+    if let Some(observers) = world.observers().try_get_observers(event_key) {
+        entities.extend(observers.global_observers().keys().copied());
+        if let Some(watching) = target.and_then(|t| observers.entity_observers().get(&t)) {
+            entities.extend(watching.keys().copied());
+        }
+    }
+    entities.sort();
+    entities.dedup();
+    let observers = entities
+        .into_iter()
+        .filter(|entity| world.get::<InspectorInternal>(*entity).is_none())
+        .map(|entity| TracedObserver {
+            entity,
+            name: observer_name(world, entity),
+        })
+        .collect();
+
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |count| count.0);
+    world.resource_mut::<ObserverTrace>().push(TriggerRecord {
+        frame,
+        event: event.to_string(),
+        target,
+        observers,
+    });
+}
+
+fn observer_name(world: &World, entity: Entity) -> String {
+    if let Some(name) = world.get::<Name>(entity) {
+        return name.to_string();
+    }
+    world.get::<Observer>(entity).map_or_else(
+        || entity.to_string(),
+        |observer| ShortName(&observer.system_name()).to_string(),
+    )
+}
@@ -1,10 +1,13 @@
 //! Core panel management for the inspector UI.
 
-use bevy::{camera::visibility::RenderLayers, light::AmbientLight, light::DirectionalLight, prelude::*};
+use bevy::{camera::visibility::RenderLayers, prelude::*};
 use bevy_egui::EguiGlobalSettings;
 use bevy_inspector_egui::bevy_egui::{EguiContext, PrimaryEguiContext};
 
-use crate::state::{GameViewportRect, InspectorEnabled, UiState};
+use crate::{
+    config::{InspectorEnabled, InspectorInternal},
+    state::{GameViewportRect, InspectorConfig, UiState},
+};
 
 /// System that renders the inspector UI.
 pub fn show_ui_system(world: &mut World) {
@@ -38,8 +41,12 @@ pub fn show_ui_system(world: &mut World) {
 }
 
 /// System to toggle the inspector panel visibility.
-pub fn toggle_inspector(keys: Res<ButtonInput<KeyCode>>, mut enabled: ResMut<InspectorEnabled>) {
-    if keys.just_pressed(KeyCode::Delete) {
+pub fn toggle_inspector(
+    keys: Res<ButtonInput<KeyCode>>,
+    config: Res<InspectorConfig>,
+    mut enabled: ResMut<InspectorEnabled>,
+) {
+    if keys.just_pressed(config.toggle_key) {
         enabled.0 = !enabled.0;
     }
 }
@@ -51,26 +58,11 @@ pub fn setup(
 ) {
     egui_global_settings.auto_create_primary_context = false;
 
-    // Ambient light for 3D scenes (optional, games can override)
-    commands.insert_resource(AmbientLight {
-        color: Color::WHITE,
-        brightness: 0.02,
-        ..default()
-    });
-
-    // Directional light
-    commands.spawn((
-        DirectionalLight {
-            illuminance: 2000.0,
-            ..default()
-        },
-        Transform::from_rotation(Quat::from_rotation_x(-std::f32::consts::PI / 2.0)),
-    ));
-
     // Egui camera (separate from game camera)
     commands.spawn((
         Camera2d,
         Name::new("Egui Camera"),
+        InspectorInternal,
         PrimaryEguiContext,
         RenderLayers::none(),
         Msaa::Off,
@@ -3,44 +3,20 @@
 use bevy::{gizmos::gizmos::Gizmos, prelude::*, window::Window};
 use bevy_inspector_egui::bevy_egui::{EguiContext, PrimaryEguiContext};
 
-use crate::state::{InspectorEnabled, InspectorSelection, UiState};
+use crate::{
+    config::{CrosshairConfig, InspectorEnabled, InspectorInternal},
+    state::{InspectorSelection, UiState},
+};
 
 /// Marker component for the crosshair visual that shows the picked entity's position.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
+#[require(InspectorInternal)]
 pub struct PickedEntityMarker;
 
 /// The size of the crosshair marker lines.
 const CROSSHAIR_SIZE: f32 = 20.0;
 
-/// Default crosshair color (green).
-const DEFAULT_CROSSHAIR_COLOR: Color = Color::srgb(0.2, 0.8, 0.2);
-
-/// Configuration for the entity selection crosshair visual.
-///
-/// # Example
-///
-/// ```ignore
-/// // Change crosshair color to red
-/// app.insert_resource(CrosshairConfig {
-///     color: Color::srgb(1.0, 0.2, 0.2),
-/// });
-/// ```
-#[derive(Resource, Clone, Debug, Reflect)]
-#[reflect(Resource)]
-pub struct CrosshairConfig {
-    /// Color of the crosshair gizmo.
-    pub color: Color,
-}
-
-impl Default for CrosshairConfig {
-    fn default() -> Self {
-        Self {
-            color: DEFAULT_CROSSHAIR_COLOR,
-        }
-    }
-}
-
 /// Automatically adds `Pickable` component to newly spawned sprites.
 pub fn auto_add_pickable_to_sprites(
     mut commands: Commands,
@@ -56,6 +32,7 @@ pub fn auto_add_pickable_to_sprites(
 /// Note: This system requires `MouseCoords` resource to be available from the game.
 /// Games should provide a system that populates world coordinates from mouse position.
 /// This is a simplified version that uses direct window cursor position.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_picking_clicks(
     mut ui_state: ResMut<UiState>,
     enabled: Res<InspectorEnabled>,
@@ -8,14 +8,29 @@
 //! - [`InspectorPlugin`] - The main plugin to add to your app
 //! - [`InspectorMainCamera`] - Marker component for viewport management
 //! - [`InspectorExt`] - Extension trait for registering custom tabs
+//! - [`InspectorCommandsExt`] - Extension trait for adding and removing tabs at runtime
 //! - [`InspectorTab`] - Trait for implementing custom tabs
 //! - [`CrosshairConfig`] - Configuration for entity selection crosshair
+//! - [`DiagnosticsConfig`] - History window and pinned diagnostics of the Diagnostics tab
+//! - [`LayoutPersistence`] - Where the dock layout is saved between runs
+//! - [`LayoutBuilder`] - Builder for named layout presets
+//! - [`PreviewLighting`] - Toggle for the inspector's optional 3D preview lighting
+//! - [`TimeControl`] - Hotkeys and step size of the pause and step controls
 //! - [`egui_pointer_over_area`] - Run condition for blocking game input over panels
+//!
+//! Without the `inspector` feature, only the no-op registration API and the
+//! plain marker and config types are available.
+
+pub use crate::{
+    BuiltinTab, CrosshairConfig, DiagnosticsConfig, DockPosition, InspectorCommandsExt,
+    InspectorEnabled, InspectorExt, InspectorInternal, InspectorMainCamera, InspectorPlugin,
+    InspectorTab, LayoutPersistence, PreviewLighting, Tab, egui_pointer_over_area,
+};
 
+#[cfg(feature = "inspector")]
 pub use crate::{
-    picking::CrosshairConfig,
-    state::{GameViewportRect, InspectorEnabled, InspectorSelection, UiState},
-    tabs::{BuiltinTab, DockPosition, InspectorExt, InspectorTab, InspectorTabRegistry, Tab},
-    viewport::{InspectorMainCamera, egui_pointer_over_area},
-    InspectorPlugin,
+    layout::LayoutBuilder,
+    state::{GameViewportRect, InspectorConfig, InspectorSelection, UiState},
+    tabs::InspectorTabRegistry,
+    time_control::{StepMode, TimeControl},
 };
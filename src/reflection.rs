@@ -0,0 +1,11 @@
+//! Helpers for reflected values shared by the inspector's modules.
+
+use bevy::reflect::PartialReflect;
+
+/// Copy a reflected value, keeping its concrete type where possible.
+pub(crate) fn clone_reflected(value: &dyn PartialReflect) -> Box<dyn PartialReflect> {
+    match value.reflect_clone() {
+        Ok(value) => value.into_partial_reflect(),
+        Err(_) => value.to_dynamic(),
+    }
+}
@@ -0,0 +1,403 @@
+//! Snapshots of each schedule's systems, sets, ordering and data access.
+//!
+//! Bevy moves a schedule's systems and run conditions out of its
+//! [`ScheduleGraph`] once it is built, so they cannot be read back afterwards.
+//! [`ScheduleGraphs::watch`] adds a build pass to every schedule that copies
+//! what the Schedules tab needs into [`ScheduleGraphs`] each time the schedule
+//! is (re)built. The same pass adds the run condition behind
+//! [`DisabledSystems`](crate::DisabledSystems). Schedules added after startup
+//! are picked up by [`watch_new_schedules`].
+
+use std::collections::HashSet;
+
+use bevy::{
+    ecs::{
+        component::ComponentId,
+        query::{Access, ComponentAccessKind},
+        schedule::{
+            ConditionWithAccess, NodeId, ScheduleBuildError, ScheduleBuildPass, ScheduleGraph,
+            ScheduleLabel, SystemKey, SystemSetKey,
+            graph::{DiGraph, Direction},
+        },
+    },
+    prelude::*,
+};
+
+use crate::system_toggle;
+
+/// Components and resources a system reads and writes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SystemAccess {
+    /// Components the system reads but does not write.
+    pub component_reads: Vec<String>,
+    /// Components the system writes.
+    pub component_writes: Vec<String>,
+    /// Resources the system reads but does not write.
+    pub resource_reads: Vec<String>,
+    /// Resources the system writes.
+    pub resource_writes: Vec<String>,
+    /// The system reads every component, like a `&World` parameter.
+    pub reads_all_components: bool,
+    /// The system writes every component, like an exclusive system.
+    pub writes_all_components: bool,
+    /// The system reads every resource.
+    pub reads_all_resources: bool,
+    /// The system writes every resource.
+    pub writes_all_resources: bool,
+}
+
+impl SystemAccess {
+    fn from_access(access: &Access, world: &World) -> Self {
+        let name_of = |id: ComponentId| {
+            world.components().get_info(id).map_or_else(
+                || format!("{id:?}"),
+                |info| info.name().shortname().to_string(),
+            )
+        };
+
+        let mut result = SystemAccess {
+            resource_reads: access.resource_reads().map(name_of).collect(),
+            resource_writes: access.resource_writes().map(name_of).collect(),
+            reads_all_components: access.has_read_all_components(),
+            writes_all_components: access.has_write_all_components(),
+            reads_all_resources: access.has_read_all_resources(),
+            writes_all_resources: access.has_write_all_resources(),
+            ..default()
+        };
+        // Unbounded access is covered by the `*_all_components` flags
+        if let Ok(components) = access.try_iter_component_access() {
+            for kind in components {
+                match kind {
+                    ComponentAccessKind::Shared(id) => result.component_reads.push(name_of(id)),
+                    ComponentAccessKind::Exclusive(id) => {
+                        result.component_writes.push(name_of(id));
+                    }
+                    ComponentAccessKind::Archetypal(_) => {}
+                }
+            }
+        }
+        for names in [
+            &mut result.component_reads,
+            &mut result.component_writes,
+            &mut result.resource_reads,
+            &mut result.resource_writes,
+        ] {
+            names.sort();
+            names.dedup();
+        }
+        result
+    }
+}
+
+/// A system as it was last built into its schedule.
+#[derive(Clone, Debug, Default)]
+pub struct SystemInfo {
+    /// Full type name of the system.
+    pub name: String,
+    /// Named system sets the system is in, looking through anonymous sets.
+    pub sets: Vec<String>,
+    /// Run conditions of the system, including those given to a tuple of
+    /// systems it was added in. Conditions of named sets are on the set.
+    pub conditions: Vec<String>,
+    /// Systems and sets this system was explicitly ordered before.
+    pub before: Vec<String>,
+    /// Systems and sets this system was explicitly ordered after.
+    pub after: Vec<String>,
+    /// Whether the system takes `&mut World`.
+    pub is_exclusive: bool,
+    /// What the system reads and writes.
+    pub access: SystemAccess,
+}
+
+/// A named system set as it was last built into its schedule.
+#[derive(Clone, Debug, Default)]
+pub struct SystemSetInfo {
+    /// Debug name of the set, e.g. `PhysicsSet::Step`.
+    pub name: String,
+    /// Named sets this set is nested in.
+    pub parents: Vec<String>,
+    /// Run conditions of the set.
+    pub conditions: Vec<String>,
+    /// Systems and sets this set was explicitly ordered before.
+    pub before: Vec<String>,
+    /// Systems and sets this set was explicitly ordered after.
+    pub after: Vec<String>,
+}
+
+/// Systems and named sets of one schedule.
+#[derive(Clone, Debug, Default)]
+pub struct ScheduleInfo {
+    /// The schedule's label, e.g. `Update`.
+    pub label: String,
+    /// Every system in the schedule.
+    pub systems: Vec<SystemInfo>,
+    /// Every named set in the schedule.
+    pub sets: Vec<SystemSetInfo>,
+}
+
+impl ScheduleInfo {
+    /// Read a schedule graph whose systems are still in it, i.e. while it is being built.
+    pub fn from_graph(label: String, graph: &ScheduleGraph, world: &World) -> Self {
+        let systems = graph
+            .systems
+            .iter()
+            .map(|(key, system, conditions)| {
+                let node = NodeId::System(key);
+                let (before, after) = ordering(graph, node);
+                let (sets, inherited) = parents(graph, node);
+                SystemInfo {
+                    name: system.name().to_string(),
+                    sets,
+                    conditions: condition_names(conditions).chain(inherited).collect(),
+                    before,
+                    after,
+                    is_exclusive: system.is_exclusive(),
+                    access: graph.systems.get(key).map_or_else(default, |system| {
+                        SystemAccess::from_access(system.access.combined_access(), world)
+                    }),
+                }
+            })
+            .collect();
+
+        let sets = graph
+            .system_sets
+            .iter()
+            .filter(|(_, set, _)| is_named(*set))
+            .map(|(key, set, conditions)| {
+                let node = NodeId::Set(key);
+                let (before, after) = ordering(graph, node);
+                let (parents, inherited) = parents(graph, node);
+                SystemSetInfo {
+                    name: format!("{set:?}"),
+                    parents,
+                    conditions: condition_names(conditions).chain(inherited).collect(),
+                    before,
+                    after,
+                }
+            })
+            .collect();
+
+        Self {
+            label,
+            systems,
+            sets,
+        }
+    }
+
+    /// The system with the given full name.
+    pub fn system(&self, name: &str) -> Option<&SystemInfo> {
+        self.systems.iter().find(|system| system.name == name)
+    }
+
+    /// The set with the given debug name.
+    pub fn set(&self, name: &str) -> Option<&SystemSetInfo> {
+        self.sets.iter().find(|set| set.name == name)
+    }
+}
+
+/// Resource holding the latest snapshot of every watched schedule, in the
+/// order they were first built.
+#[derive(Resource, Default)]
+pub struct ScheduleGraphs {
+    schedules: Vec<ScheduleInfo>,
+}
+
+impl ScheduleGraphs {
+    /// Capture every schedule in `schedules` each time it is built from now on,
+    /// and let [`DisabledSystems`](crate::DisabledSystems) turn their systems off.
+    ///
+    /// Schedules that have already been built show up after their next change.
+    /// Watching a schedule twice is harmless.
+    pub fn watch(schedules: &mut Schedules) {
+        for (label, schedule) in schedules.iter_mut() {
+            watch_schedule(label, schedule);
+        }
+    }
+
+    /// Captured schedules, in the order they were first built.
+    pub fn iter(&self) -> impl Iterator<Item = &ScheduleInfo> {
+        self.schedules.iter()
+    }
+
+    /// The schedule with the given label, e.g. `Update`.
+    pub fn get(&self, label: &str) -> Option<&ScheduleInfo> {
+        self.schedules
+            .iter()
+            .find(|schedule| schedule.label == label)
+    }
+
+    /// Number of captured schedules.
+    pub fn len(&self) -> usize {
+        self.schedules.len()
+    }
+
+    /// Whether no schedule has been captured yet.
+    pub fn is_empty(&self) -> bool {
+        self.schedules.is_empty()
+    }
+
+    fn insert(&mut self, info: ScheduleInfo) {
+        match self.schedules.iter_mut().find(|s| s.label == info.label) {
+            Some(existing) => *existing = info,
+            None => self.schedules.push(info),
+        }
+    }
+}
+
+/// System that watches schedules added to [`Schedules`] since it last ran, as
+/// [`ScheduleGraphs::watch`] does at startup.
+///
+/// A schedule that is added and run within the same frame has already been
+/// built by the time this sees it, so it shows up after its next change.
+pub fn watch_new_schedules(
+    mut schedules: ResMut<Schedules>,
+    mut seen: Local<HashSet<Box<dyn ScheduleLabel>>>,
+) {
+    // Compare labels first so `Schedules` is only changed when there is
+    // something new
+    let new = schedules
+        .iter()
+        .filter(|(label, _)| !seen.contains(*label))
+        .map(|(label, _)| label.dyn_clone())
+        .collect::<Vec<_>>();
+    if new.is_empty() {
+        return;
+    }
+    for (label, schedule) in schedules.iter_mut() {
+        if new.iter().any(|new| **new == *label) {
+            watch_schedule(label, schedule);
+        }
+    }
+    seen.extend(new);
+}
+
+fn watch_schedule(label: &dyn ScheduleLabel, schedule: &mut Schedule) {
+    // Build passes are keyed by type, so this replaces an earlier one
+    schedule.add_build_pass(CaptureSchedulePass {
+        label: format!("{label:?}"),
+    });
+}
+
+/// Build pass that copies the schedule into [`ScheduleGraphs`] and adds the
+/// system toggle run conditions.
+#[derive(Debug)]
+struct CaptureSchedulePass {
+    label: String,
+}
+
+impl ScheduleBuildPass for CaptureSchedulePass {
+    type EdgeOptions = ();
+
+    fn add_dependency(&mut self, _from: NodeId, _to: NodeId, _options: Option<&Self::EdgeOptions>) {
+    }
+
+    fn collapse_set(
+        &mut self,
+        _set: SystemSetKey,
+        _systems: &[SystemKey],
+        _dependency_flattening: &DiGraph<NodeId>,
+    ) -> impl Iterator<Item = (NodeId, NodeId)> {
+        std::iter::empty()
+    }
+
+    fn build(
+        &mut self,
+        world: &mut World,
+        graph: &mut ScheduleGraph,
+        _dependency_flattened: &mut DiGraph<SystemKey>,
+    ) -> Result<(), ScheduleBuildError> {
+        let info = ScheduleInfo::from_graph(self.label.clone(), graph, world);
+        world.get_resource_or_init::<ScheduleGraphs>().insert(info);
+        system_toggle::add_toggle_conditions(graph, world);
+        Ok(())
+    }
+}
+
+/// Whether a set has a name of its own, as opposed to the anonymous sets made
+/// by tuples of systems and the sets Bevy makes for each system function.
+pub(crate) fn is_named(set: &dyn SystemSet) -> bool {
+    !set.is_anonymous() && set.system_type().is_none()
+}
+
+fn condition_names(conditions: &[ConditionWithAccess]) -> impl Iterator<Item = String> + '_ {
+    conditions
+        .iter()
+        .map(|condition| condition.condition.name().to_string())
+        .filter(|name| name != system_toggle::TOGGLE_CONDITION)
+}
+
+/// Named sets directly containing `node`, and the run conditions of the
+/// anonymous sets in between.
+fn parents(graph: &ScheduleGraph, node: NodeId) -> (Vec<String>, Vec<String>) {
+    let mut names = Vec::new();
+    let mut conditions = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        for parent in graph
+            .hierarchy()
+            .graph()
+            .neighbors_directed(node, Direction::Incoming)
+        {
+            let NodeId::Set(key) = parent else {
+                continue;
+            };
+            let Some(set) = graph.system_sets.get(key) else {
+                continue;
+            };
+            if is_named(set) {
+                names.push(format!("{set:?}"));
+            } else if set.is_anonymous() && visited.insert(key) {
+                conditions.extend(
+                    graph
+                        .system_sets
+                        .get_conditions(key)
+                        .map(condition_names)
+                        .into_iter()
+                        .flatten(),
+                );
+                stack.push(parent);
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    (names, conditions)
+}
+
+/// Explicit `before` and `after` constraints of `node`.
+fn ordering(graph: &ScheduleGraph, node: NodeId) -> (Vec<String>, Vec<String>) {
+    let dependency = graph.dependency().graph();
+    let names = |direction| {
+        dependency
+            .neighbors_directed(node, direction)
+            .map(|other| node_name(graph, other))
+            .collect()
+    };
+    (names(Direction::Outgoing), names(Direction::Incoming))
+}
+
+/// Display name of a system or set. Unnamed sets are described by their members.
+fn node_name(graph: &ScheduleGraph, node: NodeId) -> String {
+    match node {
+        NodeId::System(key) => graph.systems.get(key).map_or_else(
+            || format!("{key:?}"),
+            |system| system.system.name().to_string(),
+        ),
+        NodeId::Set(key) => match graph.system_sets.get(key) {
+            Some(set) if is_named(set) => format!("{set:?}"),
+            _ => {
+                let members: Vec<String> = graph
+                    .hierarchy()
+                    .graph()
+                    .neighbors_directed(node, Direction::Outgoing)
+                    .map(|member| node_name(graph, member))
+                    .collect();
+                match members.as_slice() {
+                    [single] => single.clone(),
+                    _ => format!("({})", members.join(", ")),
+                }
+            }
+        },
+    }
+}
@@ -0,0 +1,140 @@
+//! Capture of frames whose frame time goes over a threshold.
+//!
+//! [`capture_frame_spikes`] checks the latest frame time in the
+//! [`DiagnosticsStore`] every frame, whether or not the inspector is visible, and
+//! records a [`FrameSpike`] with a snapshot of every diagnostic from that frame.
+//! The Diagnostics tab lists the recorded spikes.
+
+use std::collections::VecDeque;
+
+use bevy::{
+    diagnostic::{DiagnosticsStore, FrameCount, FrameTimeDiagnosticsPlugin},
+    ecs::entity::Entities,
+    platform::time::Instant,
+    prelude::*,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::{DiagnosticsConfig, InspectorInternal};
+
+/// Value of a single diagnostic at the time of a spike.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DiagnosticValue {
+    /// The diagnostic's path, e.g. `frame_time` or `physics/step_time`.
+    pub path: String,
+    /// Latest measurement when the spike was captured.
+    pub value: f64,
+    /// Unit suffix of the diagnostic, e.g. `ms`.
+    pub suffix: String,
+}
+
+/// A frame whose frame time went over [`DiagnosticsConfig::spike_threshold_ms`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FrameSpike {
+    /// Frame number from [`FrameCount`].
+    pub frame: u32,
+    /// Seconds since startup, in real time.
+    pub elapsed_secs: f64,
+    /// Frame time of the spike in milliseconds.
+    pub frame_time_ms: f64,
+    /// Entities alive in that frame, not counting the inspector's own.
+    pub entity_count: u32,
+    /// Every diagnostic with a measurement, sorted by path.
+    pub diagnostics: Vec<DiagnosticValue>,
+}
+
+/// Resource holding the most recent frame spikes, oldest first.
+///
+/// Only the last [`DiagnosticsConfig::max_spikes`] spikes are kept.
+#[derive(Resource, Default)]
+pub struct FrameSpikes {
+    spikes: VecDeque<FrameSpike>,
+}
+
+impl FrameSpikes {
+    /// Record a spike, dropping the oldest ones beyond `max_spikes`.
+    pub fn push(&mut self, spike: FrameSpike, max_spikes: usize) {
+        self.spikes.push_back(spike);
+        while self.spikes.len() > max_spikes {
+            self.spikes.pop_front();
+        }
+    }
+
+    /// Recorded spikes, oldest first.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &FrameSpike> {
+        self.spikes.iter()
+    }
+
+    /// The most recent spike.
+    pub fn latest(&self) -> Option<&FrameSpike> {
+        self.spikes.back()
+    }
+
+    /// Number of recorded spikes.
+    pub fn len(&self) -> usize {
+        self.spikes.len()
+    }
+
+    /// Whether no spikes have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.spikes.is_empty()
+    }
+
+    /// Forget all recorded spikes.
+    pub fn clear(&mut self) {
+        self.spikes.clear();
+    }
+}
+
+/// System that records a [`FrameSpike`] when the latest frame time is over the threshold.
+#[allow(clippy::too_many_arguments)]
+pub fn capture_frame_spikes(
+    store: Option<Res<DiagnosticsStore>>,
+    config: Res<DiagnosticsConfig>,
+    frame_count: Option<Res<FrameCount>>,
+    time: Res<Time<Real>>,
+    entities: &Entities,
+    q_internal: Query<(), With<InspectorInternal>>,
+    mut spikes: ResMut<FrameSpikes>,
+    mut last_checked: Local<Option<Instant>>,
+) {
+    let Some(store) = store else {
+        return;
+    };
+    let Some(measurement) = store.get_measurement(&FrameTimeDiagnosticsPlugin::FRAME_TIME) else {
+        return;
+    };
+    // Frame time is measured once per frame; don't record the same one twice
+    if *last_checked == Some(measurement.time) {
+        return;
+    }
+    *last_checked = Some(measurement.time);
+
+    if measurement.value <= config.spike_threshold_ms {
+        return;
+    }
+
+    let mut diagnostics: Vec<DiagnosticValue> = store
+        .iter()
+        .filter_map(|diagnostic| {
+            Some(DiagnosticValue {
+                path: diagnostic.path().as_str().to_string(),
+                value: diagnostic.value()?,
+                suffix: diagnostic.suffix.to_string(),
+            })
+        })
+        .collect();
+    diagnostics.sort_by(|a, b| a.path.cmp(&b.path));
+
+    spikes.push(
+        FrameSpike {
+            frame: frame_count.map_or(0, |count| count.0),
+            elapsed_secs: time.elapsed_secs_f64(),
+            frame_time_ms: measurement.value,
+            entity_count: entities.len() - q_internal.iter().count() as u32,
+            diagnostics,
+        },
+        config.max_spikes,
+    );
+}
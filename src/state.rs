@@ -7,18 +7,27 @@ use bevy_egui::egui;
 use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
 use egui_dock::{DockState, NodeIndex, Style};
 
-use crate::tabs::{BuiltinTab, InspectorTab, InspectorTabRegistry, Tab};
+use crate::{
+    config::LayoutPersistence,
+    layout::{self, LayoutError, SavedLayout},
+    tabs::{BuiltinTab, DockPosition, DuplicateTabId, InspectorTab, InspectorTabRegistry, Tab},
+};
 
-/// Resource controlling whether the inspector panel is visible.
-///
-/// Toggle with the Delete key.
-#[derive(Resource, Reflect)]
-#[reflect(Resource)]
-pub struct InspectorEnabled(pub bool);
+/// Runtime configuration of the inspector, set up by [`InspectorPlugin`](crate::InspectorPlugin).
+#[derive(Resource, Clone, Debug)]
+pub struct InspectorConfig {
+    /// Key that toggles the inspector panel.
+    pub toggle_key: KeyCode,
+    /// Built-in tabs available in the layout and the Window menu.
+    pub builtin_tabs: Vec<BuiltinTab>,
+}
 
-impl Default for InspectorEnabled {
+impl Default for InspectorConfig {
     fn default() -> Self {
-        Self(true)
+        Self {
+            toggle_key: KeyCode::Delete,
+            builtin_tabs: BuiltinTab::ALL.to_vec(),
+        }
     }
 }
 
@@ -75,8 +84,16 @@ pub struct UiState {
     pub selection: InspectorSelection,
     /// Search filter for hierarchy tab.
     pub hierarchy_search: String,
+    /// Whether the hierarchy lists entities marked with [`InspectorInternal`].
+    pub show_internal_entities: bool,
     /// Custom tabs extracted from the registry for rendering.
-    custom_tabs: Vec<Box<dyn InspectorTab>>,
+    pub(crate) custom_tabs: Vec<Box<dyn InspectorTab>>,
+    /// Built-in tabs that may appear in the dock.
+    builtin_tabs: Vec<BuiltinTab>,
+    /// Named layout presets, in the order they were registered.
+    layout_presets: Vec<(String, DockState<Tab>)>,
+    /// Name typed into the "Save current layout" field of the Layouts menu.
+    pub(crate) new_preset_name: String,
 }
 
 impl UiState {
@@ -88,46 +105,66 @@ impl UiState {
             selection: InspectorSelection::Entities,
             viewport_rect: egui::Rect::NOTHING,
             hierarchy_search: String::new(),
+            show_internal_entities: false,
             custom_tabs: Vec::new(),
+            builtin_tabs: BuiltinTab::ALL.to_vec(),
+            layout_presets: Vec::new(),
+            new_preset_name: String::new(),
         }
     }
 
     /// Build the default dock layout with built-in tabs and any custom tabs.
-    pub fn build_default_layout(&mut self, num_custom_tabs: usize) {
-        let mut state = DockState::new(vec![Tab::Builtin(BuiltinTab::GameView)]);
+    ///
+    /// Custom tabs are placed according to their [`InspectorTab::dock_position`]:
+    /// next to Hierarchy/Resources on the left, next to Inspector on the right,
+    /// tabbed with the Game view in the center, or with Assets at the bottom.
+    pub fn build_default_layout(&mut self) {
+        let mut center_tabs: Vec<Tab> = vec![Tab::Builtin(BuiltinTab::GameView)];
+        let mut left_tabs: Vec<Tab> = vec![
+            Tab::Builtin(BuiltinTab::Hierarchy),
+            Tab::Builtin(BuiltinTab::Resources),
+            Tab::Builtin(BuiltinTab::Schedules),
+        ];
+        let mut right_tabs: Vec<Tab> = vec![Tab::Builtin(BuiltinTab::Inspector)];
+        let mut bottom_tabs: Vec<Tab> = vec![
+            Tab::Builtin(BuiltinTab::Assets),
+            Tab::Builtin(BuiltinTab::Systems),
+            Tab::Builtin(BuiltinTab::States),
+            Tab::Builtin(BuiltinTab::Messages),
+            Tab::Builtin(BuiltinTab::Observers),
+            Tab::Builtin(BuiltinTab::Lifecycle),
+        ];
+
+        // Sort custom tabs into the panel they asked for
+        for tab in &self.custom_tabs {
+            let panel = match tab.dock_position() {
+                DockPosition::Left => &mut left_tabs,
+                DockPosition::Right => &mut right_tabs,
+                DockPosition::Bottom => &mut bottom_tabs,
+                DockPosition::Center => &mut center_tabs,
+            };
+            panel.push(Tab::Custom(tab.id().to_string()));
+        }
+
+        let mut state = DockState::new(center_tabs);
         let tree = state.main_surface_mut();
 
         // Right panel: Inspector
-        let [main, _] = tree.split_right(
-            NodeIndex::root(),
-            0.8,
-            vec![Tab::Builtin(BuiltinTab::Inspector)],
-        );
+        let [main, _] = tree.split_right(NodeIndex::root(), 0.8, right_tabs);
 
         // Left panel: Diagnostics at top
         let [main, left_panel] =
             tree.split_left(main, 0.2, vec![Tab::Builtin(BuiltinTab::Diagnostics)]);
 
-        // Hierarchy and Resources below diagnostics (as tabs in the same panel)
-        tree.split_below(
-            left_panel,
-            0.2,
-            vec![
-                Tab::Builtin(BuiltinTab::Hierarchy),
-                Tab::Builtin(BuiltinTab::Resources),
-            ],
-        );
-
-        // Bottom panel: Assets and custom tabs
-        let mut bottom_tabs: Vec<Tab> = vec![Tab::Builtin(BuiltinTab::Assets)];
-
-        // Add custom tabs to the bottom panel
-        for i in 0..num_custom_tabs {
-            bottom_tabs.push(Tab::Custom(i));
-        }
+        // Hierarchy, Resources, Schedules and left-docked custom tabs below diagnostics
+        tree.split_below(left_panel, 0.2, left_tabs);
 
+        // Bottom panel: Assets, Systems, States, Messages, Observers, Lifecycle and
+        // bottom-docked custom tabs
         tree.split_below(main, 0.8, bottom_tabs);
 
+        // Drop built-in tabs the game opted out of
+        self.retain_known_tabs(&mut state);
         self.state = state;
     }
 
@@ -136,14 +173,238 @@ impl UiState {
         self.custom_tabs = tabs;
     }
 
-    /// Render the dock UI.
+    /// Ids of all custom tabs known to the inspector.
+    pub fn custom_tab_ids(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.custom_tabs.iter().map(|tab| tab.id())
+    }
+
+    /// Add a custom tab at its preferred dock position.
+    ///
+    /// Fails if a custom tab with the same id is already present.
+    pub fn add_custom_tab(&mut self, tab: Box<dyn InspectorTab>) -> Result<(), DuplicateTabId> {
+        if self.custom_tab_ids().any(|id| id == tab.id()) {
+            return Err(DuplicateTabId(tab.id()));
+        }
+        self.push_tab_at(Tab::Custom(tab.id().to_string()), tab.dock_position());
+        self.custom_tabs.push(tab);
+        Ok(())
+    }
+
+    /// Remove a custom tab and close it in the dock, returning the removed tab.
+    pub fn remove_custom_tab(&mut self, id: &str) -> Option<Box<dyn InspectorTab>> {
+        let index = self.custom_tabs.iter().position(|tab| tab.id() == id)?;
+        self.close_tab(&Tab::Custom(id.to_string()));
+        Some(self.custom_tabs.remove(index))
+    }
+
+    /// Replace the custom tab with the same id as `tab`, keeping its place in the dock.
+    ///
+    /// If no such tab exists, `tab` is added at its preferred dock position.
+    /// Returns the tab that was replaced.
+    pub fn replace_custom_tab(
+        &mut self,
+        tab: Box<dyn InspectorTab>,
+    ) -> Option<Box<dyn InspectorTab>> {
+        match self.custom_tabs.iter_mut().find(|existing| existing.id() == tab.id()) {
+            Some(existing) => Some(std::mem::replace(existing, tab)),
+            None => {
+                // Cannot fail: we just checked that the id is free
+                let _ = self.add_custom_tab(tab);
+                None
+            }
+        }
+    }
+
+    /// Make `tab` the active tab of its panel and focus that panel.
+    ///
+    /// Returns `false` if the tab is not currently open.
+    pub fn focus_tab(&mut self, tab: &Tab) -> bool {
+        let Some((surface, node, index)) = self.state.find_tab(tab) else {
+            return false;
+        };
+        self.state.set_active_tab((surface, node, index));
+        self.state.set_focused_node_and_surface((surface, node));
+        true
+    }
+
+    /// Whether `tab` is currently open in the dock.
+    pub fn is_tab_open(&self, tab: &Tab) -> bool {
+        self.state.find_tab(tab).is_some()
+    }
+
+    /// Open `tab` at its preferred dock position, or focus it if it is already open.
+    ///
+    /// Returns `false` if `tab` is a disabled built-in tab or an unknown custom tab.
+    pub fn open_tab(&mut self, tab: Tab) -> bool {
+        if self.focus_tab(&tab) {
+            return true;
+        }
+        let position = match &tab {
+            Tab::Builtin(builtin) if !self.builtin_tabs.contains(builtin) => return false,
+            Tab::Builtin(builtin) => builtin.dock_position(),
+            Tab::Custom(id) => match self.custom_tabs.iter().find(|custom| custom.id() == id) {
+                Some(custom) => custom.dock_position(),
+                None => return false,
+            },
+        };
+        self.push_tab_at(tab.clone(), position);
+        self.focus_tab(&tab)
+    }
+
+    /// Close every occurrence of `tab` in the dock.
+    pub fn close_tab(&mut self, tab: &Tab) {
+        self.state.retain_tabs(|open| open != tab);
+    }
+
+    /// Add `tab` to the panel that holds the built-in tabs matching `position`.
+    ///
+    /// Falls back to the first leaf if that panel has been closed.
+    pub fn push_tab_at(&mut self, tab: Tab, position: DockPosition) {
+        let anchors: &[BuiltinTab] = match position {
+            DockPosition::Left => &[
+                BuiltinTab::Hierarchy,
+                BuiltinTab::Resources,
+                BuiltinTab::Diagnostics,
+            ],
+            DockPosition::Right => &[BuiltinTab::Inspector],
+            DockPosition::Bottom => &[
+                BuiltinTab::Assets,
+                BuiltinTab::Systems,
+                BuiltinTab::States,
+                BuiltinTab::Messages,
+                BuiltinTab::Observers,
+                BuiltinTab::Lifecycle,
+            ],
+            DockPosition::Center => &[BuiltinTab::GameView],
+        };
+
+        let anchor = anchors
+            .iter()
+            .find_map(|anchor| self.state.find_tab(&Tab::Builtin(*anchor)));
+        match anchor {
+            Some((surface, node, _)) => self.state[surface][node].append_tab(tab),
+            None => self.state.push_to_first_leaf(tab),
+        }
+    }
+
+    /// Register a named layout preset, replacing any preset with the same name.
+    ///
+    /// Presets are listed in the Layouts menu and the first nine can be applied
+    /// with Ctrl+1 to Ctrl+9. Build them with [`LayoutBuilder`](crate::LayoutBuilder)
+    /// or by hand as a [`DockState`].
+    pub fn register_layout_preset(&mut self, name: impl Into<String>, layout: DockState<Tab>) {
+        let name = name.into();
+        match self.layout_presets.iter_mut().find(|(existing, _)| *existing == name) {
+            Some((_, existing)) => *existing = layout,
+            None => self.layout_presets.push((name, layout)),
+        }
+    }
+
+    /// Save the current arrangement as a named preset.
+    pub fn save_layout_preset(&mut self, name: impl Into<String>) {
+        self.register_layout_preset(name, self.state.clone());
+    }
+
+    /// Remove a layout preset. Returns `false` if no preset has that name.
+    pub fn remove_layout_preset(&mut self, name: &str) -> bool {
+        let len = self.layout_presets.len();
+        self.layout_presets.retain(|(existing, _)| existing != name);
+        self.layout_presets.len() != len
+    }
+
+    /// Switch to a layout preset. Returns `false` if no preset has that name.
+    ///
+    /// Custom tabs in the preset that are not registered are left out.
+    pub fn apply_layout_preset(&mut self, name: &str) -> bool {
+        let Some((_, layout)) = self.layout_presets.iter().find(|(existing, _)| existing == name)
+        else {
+            return false;
+        };
+        let mut layout = layout.clone();
+        self.retain_known_tabs(&mut layout);
+        self.state = layout;
+        true
+    }
+
+    /// Names of all layout presets, in registration order.
+    pub fn layout_preset_names(&self) -> impl Iterator<Item = &str> {
+        self.layout_presets.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Snapshot the current dock layout and presets for saving.
+    pub fn saved_layout(&self) -> SavedLayout {
+        SavedLayout {
+            dock: self.state.clone(),
+            known_tabs: self
+                .custom_tabs
+                .iter()
+                .map(|tab| tab.id().to_string())
+                .collect(),
+            presets: self.layout_presets.clone(),
+        }
+    }
+
+    /// Replace the dock layout with a previously saved one.
+    ///
+    /// Custom tabs that are no longer registered are dropped, and tabs registered
+    /// since the layout was saved are placed at their preferred dock position.
+    /// Saved presets are added alongside any presets already registered.
+    pub fn restore_layout(&mut self, layout: SavedLayout) {
+        let SavedLayout {
+            mut dock,
+            known_tabs,
+            presets,
+        } = layout;
+
+        self.retain_known_tabs(&mut dock);
+        self.state = dock;
+
+        for (name, preset) in presets {
+            self.register_layout_preset(name, preset);
+        }
+
+        let new_tabs: Vec<_> = self
+            .custom_tabs
+            .iter()
+            .filter(|tab| !known_tabs.iter().any(|id| id == tab.id()))
+            .map(|tab| (Tab::Custom(tab.id().to_string()), tab.dock_position()))
+            .collect();
+        for (tab, position) in new_tabs {
+            self.push_tab_at(tab, position);
+        }
+    }
+
+    /// Built-in tabs that may appear in the dock.
+    pub fn builtin_tabs(&self) -> &[BuiltinTab] {
+        &self.builtin_tabs
+    }
+
+    /// Restrict which built-in tabs may appear in the dock.
+    ///
+    /// Takes effect the next time a layout is built, restored or applied.
+    pub fn set_builtin_tabs(&mut self, tabs: impl IntoIterator<Item = BuiltinTab>) {
+        self.builtin_tabs = tabs.into_iter().collect();
+    }
+
+    /// Drop disabled built-in tabs and unregistered custom tabs from `dock`.
+    fn retain_known_tabs(&self, dock: &mut DockState<Tab>) {
+        dock.retain_tabs(|tab| match tab {
+            Tab::Builtin(builtin) => self.builtin_tabs.contains(builtin),
+            Tab::Custom(id) => self.custom_tabs.iter().any(|custom| custom.id() == id),
+        });
+    }
+
+    /// Render the menu bar and dock UI.
     pub fn ui(&mut self, world: &mut World, ctx: &mut egui::Context) {
+        crate::menu::show(ctx, self, world);
+
         let mut tab_viewer = crate::tabs::TabViewer {
             world,
             viewport_rect: &mut self.viewport_rect,
             selected_entities: &mut self.selected_entities,
             selection: &mut self.selection,
             hierarchy_search: &mut self.hierarchy_search,
+            show_internal_entities: &mut self.show_internal_entities,
             custom_tabs: &mut self.custom_tabs,
         };
         egui_dock::DockArea::new(&mut self.state)
@@ -159,18 +420,34 @@ impl Default for UiState {
 }
 
 /// System to initialize UiState after InspectorTabRegistry is populated.
-pub fn initialize_ui_state(mut commands: Commands, mut registry: ResMut<InspectorTabRegistry>) {
+pub fn initialize_ui_state(
+    mut commands: Commands,
+    mut registry: ResMut<InspectorTabRegistry>,
+    persistence: Res<LayoutPersistence>,
+    config: Option<Res<InspectorConfig>>,
+) {
     // Take ownership of the custom tabs from the registry
     let custom_tabs: Vec<Box<dyn InspectorTab>> = std::mem::take(&mut registry.tabs);
-    let num_custom_tabs = custom_tabs.len();
 
     let mut ui_state = UiState::new();
-
-    // Build default layout with built-in tabs and custom tabs
-    ui_state.build_default_layout(num_custom_tabs);
+    if let Some(config) = config {
+        ui_state.set_builtin_tabs(config.builtin_tabs.iter().copied());
+    }
 
     // Store the custom tabs in UiState for rendering
     ui_state.set_custom_tabs(custom_tabs);
 
+    // Build default layout with built-in tabs and custom tabs at their dock positions
+    ui_state.build_default_layout();
+
+    // Restore the layout from the previous run, if there is one
+    if let Some(path) = persistence.path.as_deref() {
+        match layout::load_layout(path) {
+            Ok(saved) => ui_state.restore_layout(saved),
+            Err(LayoutError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => warn!("Failed to load inspector layout from {}: {err}", path.display()),
+        }
+    }
+
     commands.insert_resource(ui_state);
 }
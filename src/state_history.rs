@@ -0,0 +1,207 @@
+//! Current values and recent transitions of the game's [`States`].
+//!
+//! States are found through the type registry, so only those registered with
+//! `register_type_state` or `register_type_mutable_state` are tracked.
+//! [`track_states`] compares each state's value with the last frame's and
+//! records every change in [`StateHistory`] for the States tab.
+
+use std::{any::TypeId, collections::VecDeque};
+
+use bevy::{
+    diagnostic::FrameCount,
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, ReflectFromReflect, TypeInfo, VariantInfo},
+    state::reflect::{ReflectFreelyMutableState, ReflectState},
+};
+
+/// One change of a state's value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateTransitionRecord {
+    /// Frame number from [`FrameCount`] in which the change was seen.
+    pub frame: u32,
+    /// Value before the change, or `None` if the state did not exist.
+    pub from: Option<String>,
+    /// Value after the change, or `None` if the state was removed.
+    pub to: Option<String>,
+}
+
+/// A registered state type and its recent transitions.
+#[derive(Clone, Debug)]
+pub struct TrackedState {
+    /// Short type path of the state, e.g. `GameState`.
+    pub name: String,
+    /// Type of the state.
+    pub type_id: TypeId,
+    /// Current value, or `None` while the state does not exist.
+    pub current: Option<String>,
+    /// Whether a transition can be forced through [`NextState`].
+    ///
+    /// Computed states and states registered with `register_type_state`
+    /// are read-only.
+    pub can_set: bool,
+    /// Unit variants the state can be set to, in declaration order.
+    pub variants: Vec<String>,
+    transitions: VecDeque<StateTransitionRecord>,
+}
+
+impl TrackedState {
+    /// Recent transitions, oldest first.
+    pub fn transitions(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = &StateTransitionRecord> + ExactSizeIterator {
+        self.transitions.iter()
+    }
+}
+
+/// Resource holding every registered state and its recent transitions.
+#[derive(Resource, Clone, Debug)]
+pub struct StateHistory {
+    states: Vec<TrackedState>,
+    max_transitions: usize,
+}
+
+impl Default for StateHistory {
+    fn default() -> Self {
+        Self::new(50)
+    }
+}
+
+impl StateHistory {
+    /// Keep the last `max_transitions` transitions of each state.
+    pub fn new(max_transitions: usize) -> Self {
+        Self {
+            states: Vec::new(),
+            max_transitions: max_transitions.max(1),
+        }
+    }
+
+    /// Every state seen so far, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = &TrackedState> {
+        self.states.iter()
+    }
+
+    /// The state with the given short type path.
+    pub fn get(&self, name: &str) -> Option<&TrackedState> {
+        self.states.iter().find(|state| state.name == name)
+    }
+
+    /// Number of tracked states.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Whether no state is registered for reflection.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Record the value of a state in the given frame, adding a transition if
+    /// it changed.
+    fn observe(&mut self, frame: u32, seen: SeenState) {
+        let index = match self.states.iter().position(|s| s.type_id == seen.type_id) {
+            Some(index) => index,
+            None => {
+                let index = self.states.partition_point(|state| state.name < seen.name);
+                self.states.insert(
+                    index,
+                    TrackedState {
+                        name: seen.name,
+                        type_id: seen.type_id,
+                        current: None,
+                        can_set: seen.can_set,
+                        variants: seen.variants,
+                        transitions: VecDeque::new(),
+                    },
+                );
+                index
+            }
+        };
+
+        let state = &mut self.states[index];
+        if state.current == seen.value {
+            return;
+        }
+        let from = std::mem::replace(&mut state.current, seen.value.clone());
+        state.transitions.push_back(StateTransitionRecord {
+            frame,
+            from,
+            to: seen.value,
+        });
+        while state.transitions.len() > self.max_transitions {
+            state.transitions.pop_front();
+        }
+    }
+}
+
+/// A state's value as read in one frame.
+struct SeenState {
+    type_id: TypeId,
+    name: String,
+    value: Option<String>,
+    can_set: bool,
+    variants: Vec<String>,
+}
+
+/// System that records changes of every reflected state in [`StateHistory`].
+pub fn track_states(world: &mut World) {
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |count| count.0);
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let seen: Vec<SeenState> = registry
+        .iter()
+        .filter_map(|registration| {
+            let state = registration.data::<ReflectState>()?;
+            Some(SeenState {
+                type_id: registration.type_id(),
+                name: registration
+                    .type_info()
+                    .type_path_table()
+                    .short_path()
+                    .to_string(),
+                value: state.reflect(world).map(|value| format!("{value:?}")),
+                can_set: registration.data::<ReflectFreelyMutableState>().is_some(),
+                variants: unit_variants(registration.type_info()),
+            })
+        })
+        .collect();
+
+    let mut history = world.resource_mut::<StateHistory>();
+    for state in seen {
+        history.observe(frame, state);
+    }
+}
+
+/// Unit variants of an enum, in declaration order.
+fn unit_variants(info: &TypeInfo) -> Vec<String> {
+    let TypeInfo::Enum(info) = info else {
+        return Vec::new();
+    };
+    info.iter()
+        .filter(|variant| matches!(variant, VariantInfo::Unit(_)))
+        .map(|variant| variant.name().to_string())
+        .collect()
+}
+
+/// Queue a transition of the state `type_id` to its unit variant `variant`
+/// through its [`NextState`]. Returns false if the state cannot be set that way.
+pub(crate) fn set_next_state(world: &mut World, type_id: TypeId, variant: &str) -> bool {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let Some(registration) = registry.get(type_id) else {
+        return false;
+    };
+    let (Some(from_reflect), Some(mutable)) = (
+        registration.data::<ReflectFromReflect>(),
+        registration.data::<ReflectFreelyMutableState>(),
+    ) else {
+        return false;
+    };
+    let Some(value) = from_reflect.from_reflect(&DynamicEnum::new(variant, DynamicVariant::Unit))
+    else {
+        return false;
+    };
+    mutable.set_next_state(world, value.as_ref(), &registry);
+    true
+}
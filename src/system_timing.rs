@@ -0,0 +1,323 @@
+//! Per-system run times, read from Bevy's `system` tracing spans.
+//!
+//! Bevy only creates these spans with its `trace` feature, which this crate's
+//! `trace` feature turns on. [`system_timing_layer`] plugs into
+//! [`LogPlugin::custom_layer`](bevy::log::LogPlugin::custom_layer) and adds up
+//! how long each system spent inside its span; [`collect_system_timings`] moves
+//! those totals into [`SystemTimings`] once per frame for the Systems tab.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
+
+use bevy::{
+    ecs::schedule::{InternedScheduleLabel, NodeId, graph::Direction},
+    log::{
+        BoxedLayer,
+        tracing::{
+            Subscriber,
+            field::{Field, Visit},
+            span::{Attributes, Id},
+        },
+        tracing_subscriber::{Layer, layer::Context, registry::LookupSpan},
+    },
+    platform::time::Instant,
+    prelude::*,
+};
+
+/// Run times reported by the tracing layer since the last frame was collected.
+///
+/// Shared between the [`system_timing_layer`] and [`collect_system_timings`].
+#[derive(Resource, Clone, Default)]
+pub struct SystemTimingSink {
+    runs: Arc<Mutex<HashMap<String, Duration>>>,
+}
+
+impl SystemTimingSink {
+    /// Add one run of the system called `name`.
+    pub fn record(&self, name: &str, elapsed: Duration) {
+        let mut runs = self.runs.lock().unwrap_or_else(PoisonError::into_inner);
+        match runs.get_mut(name) {
+            Some(total) => *total += elapsed,
+            None => {
+                runs.insert(name.to_string(), elapsed);
+            }
+        }
+    }
+
+    /// Take the time recorded for each system since the last call.
+    fn take(&self) -> HashMap<String, Duration> {
+        std::mem::take(&mut *self.runs.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// Tracing layer for [`LogPlugin::custom_layer`](bevy::log::LogPlugin::custom_layer)
+/// that records how long each system runs.
+///
+/// Needs the `trace` feature; without it Bevy creates no system spans and the
+/// Systems tab stays empty.
+///
+/// ```ignore
+/// app.add_plugins(DefaultPlugins.set(LogPlugin {
+///     custom_layer: msg_inspector::system_timing_layer,
+///     ..default()
+/// }));
+/// ```
+pub fn system_timing_layer(app: &mut App) -> Option<BoxedLayer> {
+    let sink = app
+        .world_mut()
+        .get_resource_or_init::<SystemTimingSink>()
+        .clone();
+    Some(Box::new(SystemTimingLayer { sink }))
+}
+
+struct SystemTimingLayer {
+    sink: SystemTimingSink,
+}
+
+/// Span extension for a system span.
+struct SystemSpan {
+    name: String,
+    entered: Option<Instant>,
+}
+
+/// Reads the `name` field of a system span.
+#[derive(Default)]
+struct NameVisitor(Option<String>);
+
+impl Visit for NameVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "name" {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "name" {
+            self.0 = Some(format!("{value:?}"));
+        }
+    }
+}
+
+impl<S> Layer<S> for SystemTimingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let metadata = attrs.metadata();
+        if metadata.name() != "system" || !metadata.target().starts_with("bevy_ecs") {
+            return;
+        }
+        let mut visitor = NameVisitor::default();
+        attrs.record(&mut visitor);
+        if let (Some(name), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(SystemSpan {
+                name,
+                entered: None,
+            });
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(system) = span.extensions_mut().get_mut::<SystemSpan>()
+        {
+            system.entered = Some(Instant::now());
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id)
+            && let Some(system) = span.extensions_mut().get_mut::<SystemSpan>()
+            && let Some(entered) = system.entered.take()
+        {
+            self.sink.record(&system.name, entered.elapsed());
+        }
+    }
+}
+
+/// Rolling per-frame run time of one system.
+#[derive(Clone, Debug)]
+pub struct SystemTiming {
+    /// Full type name of the system, as Bevy reports it.
+    pub name: String,
+    /// Schedule the system was found in, e.g. `Update`.
+    ///
+    /// `None` until the system has been looked up, and for systems outside the
+    /// main world's schedules.
+    pub schedule: Option<String>,
+    /// Named system sets the system is directly part of.
+    pub sets: Vec<String>,
+    history: VecDeque<f64>,
+}
+
+impl SystemTiming {
+    /// Time spent in the system each frame in milliseconds, oldest first.
+    ///
+    /// Frames in which the system did not run count as zero.
+    pub fn history(&self) -> impl ExactSizeIterator<Item = f64> + '_ {
+        self.history.iter().copied()
+    }
+
+    /// Time spent in the system in the latest frame, in milliseconds.
+    pub fn last_ms(&self) -> f64 {
+        self.history.back().copied().unwrap_or_default()
+    }
+
+    /// Mean time per frame over the history, in milliseconds.
+    pub fn mean_ms(&self) -> f64 {
+        if self.history.is_empty() {
+            return 0.0;
+        }
+        self.history.iter().sum::<f64>() / self.history.len() as f64
+    }
+
+    /// Longest frame over the history, in milliseconds.
+    pub fn max_ms(&self) -> f64 {
+        self.history.iter().copied().fold(0.0, f64::max)
+    }
+}
+
+/// Resource holding the recent run times of every system that was timed.
+#[derive(Resource, Clone, Debug)]
+pub struct SystemTimings {
+    systems: Vec<SystemTiming>,
+    history_length: usize,
+}
+
+impl Default for SystemTimings {
+    fn default() -> Self {
+        Self::new(120)
+    }
+}
+
+impl SystemTimings {
+    /// Keep the last `history_length` frames for each system.
+    pub fn new(history_length: usize) -> Self {
+        Self {
+            systems: Vec::new(),
+            history_length: history_length.max(1),
+        }
+    }
+
+    /// Number of frames kept for each system.
+    pub fn history_length(&self) -> usize {
+        self.history_length
+    }
+
+    /// Add one frame of run times, summed per system.
+    ///
+    /// Known systems missing from `runs` get a zero for the frame.
+    pub fn record_frame(&mut self, runs: impl IntoIterator<Item = (String, Duration)>) {
+        let mut runs: HashMap<String, Duration> = runs.into_iter().collect();
+        for system in &mut self.systems {
+            let ms = runs
+                .remove(&system.name)
+                .map_or(0.0, |elapsed| elapsed.as_secs_f64() * 1000.0);
+            system.history.push_back(ms);
+            while system.history.len() > self.history_length {
+                system.history.pop_front();
+            }
+        }
+        for (name, elapsed) in runs {
+            self.systems.push(SystemTiming {
+                name,
+                schedule: None,
+                sets: Vec::new(),
+                history: VecDeque::from([elapsed.as_secs_f64() * 1000.0]),
+            });
+        }
+    }
+
+    /// Every timed system, in the order they were first seen.
+    pub fn iter(&self) -> impl Iterator<Item = &SystemTiming> {
+        self.systems.iter()
+    }
+
+    /// The timing of the system with the given full name.
+    pub fn get(&self, name: &str) -> Option<&SystemTiming> {
+        self.systems.iter().find(|system| system.name == name)
+    }
+
+    /// Number of timed systems.
+    pub fn len(&self) -> usize {
+        self.systems.len()
+    }
+
+    /// Whether no system has been timed yet.
+    pub fn is_empty(&self) -> bool {
+        self.systems.is_empty()
+    }
+
+    /// Forget all recorded timings.
+    pub fn clear(&mut self) {
+        self.systems.clear();
+    }
+}
+
+/// System that moves the run times gathered by the tracing layer into
+/// [`SystemTimings`], one frame at a time.
+pub fn collect_system_timings(
+    sink: Option<Res<SystemTimingSink>>,
+    mut timings: ResMut<SystemTimings>,
+) {
+    if let Some(sink) = sink {
+        timings.record_frame(sink.take());
+    }
+}
+
+/// System that looks up the schedule and system sets of newly timed systems.
+///
+/// A schedule is taken out of [`Schedules`] while it runs, so this runs in
+/// both `First` and `Last` to see every schedule at least once. Each schedule
+/// is searched again whenever new systems were timed since it was last
+/// searched. Systems that are in no schedule of the main world, like those of
+/// the render world, keep no schedule.
+///
+/// The tracing spans only carry the system's name, so systems with the same
+/// name in several schedules share one timing, listed under the first
+/// schedule they are found in.
+pub fn locate_timed_systems(
+    schedules: Res<Schedules>,
+    mut timings: ResMut<SystemTimings>,
+    mut searched: Local<HashMap<InternedScheduleLabel, usize>>,
+) {
+    let timed = timings.systems.len();
+    for (label, schedule) in schedules.iter() {
+        // Timed systems are only ever appended, so only look again when new ones show up
+        if searched.insert(schedule.label(), timed) == Some(timed) {
+            continue;
+        }
+        // Schedules that have never run have no timed systems either
+        let Ok(systems) = schedule.systems() else {
+            continue;
+        };
+        let graph = schedule.graph();
+        for (key, system) in systems {
+            let name = system.name().to_string();
+            let Some(timing) = timings
+                .systems
+                .iter_mut()
+                .find(|timing| timing.schedule.is_none() && timing.name == name)
+            else {
+                continue;
+            };
+
+            timing.schedule = Some(format!("{label:?}"));
+            timing.sets = graph
+                .hierarchy()
+                .graph()
+                .neighbors_directed(NodeId::System(key), Direction::Incoming)
+                .filter_map(|parent| match parent {
+                    NodeId::Set(set) => graph.system_sets.get(set),
+                    NodeId::System(_) => None,
+                })
+                .filter(|set| !set.is_anonymous() && set.system_type().is_none())
+                .map(|set| format!("{set:?}"))
+                .collect();
+        }
+    }
+}
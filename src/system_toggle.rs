@@ -0,0 +1,225 @@
+//! Turning systems and system sets off at runtime.
+//!
+//! The schedule build pass of [`ScheduleGraphs`](crate::ScheduleGraphs) gives
+//! every system one extra run condition that checks [`DisabledSystems`] for the
+//! system's name and the names of the sets it is in. The Schedules tab edits
+//! that resource, and a banner is shown while anything is turned off.
+
+use std::collections::{BTreeSet, HashSet};
+
+use bevy::{
+    ecs::schedule::{ConditionWithAccess, NodeId, ScheduleGraph, graph::Direction},
+    prelude::*,
+};
+use bevy_egui::egui;
+use bevy_inspector_egui::bevy_egui::{EguiContext, PrimaryEguiContext};
+
+use crate::{config::InspectorEnabled, schedule_graph::is_named};
+
+/// Name of the run condition added to every system.
+pub(crate) const TOGGLE_CONDITION: &str = "msg_inspector::system_toggle";
+
+/// Systems and system sets that are turned off for the rest of the session.
+///
+/// Entries are full system names (as listed in the Schedules tab) or set debug
+/// names like `PhysicsSet`. A name is turned off in every schedule it is in.
+/// The inspector's own systems cannot be turned off.
+///
+/// ```ignore
+/// // Start with the AI turned off
+/// app.insert_resource(DisabledSystems::from_iter(["AiSet"]));
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisabledSystems {
+    names: BTreeSet<String>,
+}
+
+impl FromIterator<String> for DisabledSystems {
+    fn from_iter<I: IntoIterator<Item = String>>(iter: I) -> Self {
+        Self {
+            names: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a str> for DisabledSystems {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(iter: I) -> Self {
+        iter.into_iter().map(str::to_string).collect()
+    }
+}
+
+impl DisabledSystems {
+    /// Turn off the system or set called `name`.
+    pub fn disable(&mut self, name: impl Into<String>) {
+        self.names.insert(name.into());
+    }
+
+    /// Turn the system or set called `name` back on.
+    pub fn enable(&mut self, name: &str) {
+        self.names.remove(name);
+    }
+
+    /// Flip the system or set called `name` between on and off.
+    pub fn toggle(&mut self, name: &str) {
+        if !self.names.remove(name) {
+            self.names.insert(name.to_string());
+        }
+    }
+
+    /// Whether `name` itself is turned off, not counting the sets it is in.
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.names.contains(name)
+    }
+
+    /// Whether any of `names` is turned off.
+    pub fn any_disabled<S: AsRef<str>>(&self, names: &[S]) -> bool {
+        !self.names.is_empty() && names.iter().any(|name| self.is_disabled(name.as_ref()))
+    }
+
+    /// Names that are turned off, sorted.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Number of names that are turned off.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether everything is turned on.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Turn everything back on.
+    pub fn clear(&mut self) {
+        self.names.clear();
+    }
+}
+
+/// Whether the system is one of the inspector's own, which stay on.
+pub(crate) fn is_inspector_system(name: &str) -> bool {
+    name.starts_with("msg_inspector::")
+}
+
+/// Give each system of a schedule that is being built a run condition that
+/// skips it while it or one of its sets is in [`DisabledSystems`].
+pub(crate) fn add_toggle_conditions(graph: &mut ScheduleGraph, world: &mut World) {
+    let systems: Vec<_> = graph
+        .systems
+        .iter()
+        .filter(|(_, system, conditions)| {
+            !is_inspector_system(&system.name().to_string())
+                // Rebuilt schedules still have the condition from the last build
+                && !conditions
+                    .iter()
+                    .any(|condition| condition.condition.name().to_string() == TOGGLE_CONDITION)
+        })
+        .map(|(key, system, _)| {
+            let mut names = named_ancestors(graph, NodeId::System(key));
+            names.push(system.name().to_string());
+            (key, names)
+        })
+        .collect();
+
+    for (key, names) in systems {
+        let condition = IntoSystem::into_system(move |disabled: Option<Res<DisabledSystems>>| {
+            disabled.is_none_or(|disabled| !disabled.any_disabled(&names))
+        })
+        .with_name(TOGGLE_CONDITION);
+        let mut condition = ConditionWithAccess::new(Box::new(condition));
+        condition.access = condition.condition.initialize(world);
+        if let Some(conditions) = graph.systems.get_conditions_mut(key) {
+            conditions.push(condition);
+        }
+    }
+}
+
+/// Every named set `node` is in, directly or through other sets.
+fn named_ancestors(graph: &ScheduleGraph, node: NodeId) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        for parent in graph
+            .hierarchy()
+            .graph()
+            .neighbors_directed(node, Direction::Incoming)
+        {
+            let NodeId::Set(key) = parent else {
+                continue;
+            };
+            if !visited.insert(key) {
+                continue;
+            }
+            if let Some(set) = graph.system_sets.get(key)
+                && is_named(set)
+            {
+                names.push(format!("{set:?}"));
+            }
+            stack.push(parent);
+        }
+    }
+    names
+}
+
+/// Contents of the "systems disabled" banner. Returns true if "Enable all" was clicked.
+pub(crate) fn banner_ui(ui: &mut egui::Ui, disabled: &DisabledSystems) -> bool {
+    let names: Vec<String> = disabled.iter().map(|n| ShortName(n).to_string()).collect();
+    let mut enable_all = false;
+    ui.horizontal(|ui| {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            format!("⚠ {} turned off: {}", plural(names.len()), names.join(", ")),
+        );
+        enable_all = ui.small_button("Enable all").clicked();
+    });
+    enable_all
+}
+
+fn plural(count: usize) -> String {
+    if count == 1 {
+        "1 system or set".to_string()
+    } else {
+        format!("{count} systems or sets")
+    }
+}
+
+/// System that shows the "systems disabled" banner over the game while the
+/// inspector is hidden. The open inspector shows it below its menu bar.
+pub fn show_disabled_systems_overlay(world: &mut World) {
+    if world
+        .get_resource::<InspectorEnabled>()
+        .is_some_and(|enabled| enabled.0)
+    {
+        return;
+    }
+    let Some(disabled) = world.get_resource::<DisabledSystems>() else {
+        return;
+    };
+    if disabled.is_empty() {
+        return;
+    }
+    let disabled = disabled.clone();
+
+    let Ok(mut egui_context) = world
+        .query_filtered::<&mut EguiContext, With<PrimaryEguiContext>>()
+        .single_mut(world)
+    else {
+        return;
+    };
+    let ctx = egui_context.get_mut().clone();
+
+    let mut enable_all = false;
+    egui::Area::new(egui::Id::new("disabled_systems_overlay"))
+        .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 8.0))
+        .order(egui::Order::Foreground)
+        .show(&ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                enable_all = banner_ui(ui, &disabled);
+            });
+        });
+    if enable_all {
+        world.resource_mut::<DisabledSystems>().clear();
+    }
+}
@@ -1,58 +1,676 @@
 //! Performance diagnostics tab.
 //!
-//! Displays FPS, frame time, and entity counts.
+//! Reads every diagnostic in Bevy's [`DiagnosticsStore`]. Frame time and entity
+//! counts get smoothed values, statistics over a time window, and history
+//! graphs; all other diagnostics are listed by path prefix with a sparkline
+//! each, and can be pinned to the top of the tab. Frames recorded by
+//! [`capture_frame_spikes`](crate::capture_frame_spikes) are listed under
+//! Frame Spikes.
 
-use bevy::prelude::*;
+use std::{ops::RangeInclusive, time::Duration};
+
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticsStore, EntityCountDiagnosticsPlugin, FrameTimeDiagnosticsPlugin,
+    },
+    platform::time::Instant,
+    prelude::*,
+};
 use bevy_egui::egui;
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+
+use super::entity_stats;
+use crate::{
+    config::{DiagnosticsConfig, InspectorInternal},
+    export::{ExportFormat, export_diagnostics},
+    spikes::FrameSpikes,
+    state::InspectorSelection,
+};
+
+/// Group shown for diagnostics whose path has no prefix.
+const UNGROUPED: &str = "General";
+
+/// Frame time budgets drawn as reference lines, in milliseconds.
+const FRAME_BUDGETS_MS: [f64; 2] = [1000.0 / 60.0, 1000.0 / 30.0];
+
+/// Statistics of a diagnostic's measurements over a time window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DiagnosticStats {
+    /// Number of measurements in the window.
+    pub samples: usize,
+    /// Mean of the measurements.
+    pub average: f64,
+    /// Smallest measurement.
+    pub min: f64,
+    /// Largest measurement.
+    pub max: f64,
+    /// Median.
+    pub p50: f64,
+    /// 95th percentile.
+    pub p95: f64,
+    /// 99th percentile.
+    pub p99: f64,
+}
+
+impl DiagnosticStats {
+    /// Compute statistics over the measurements taken within `window` of the latest one.
+    ///
+    /// Returns `None` when the diagnostic has no finite measurements.
+    pub fn over_window(diagnostic: &Diagnostic, window: Duration) -> Option<Self> {
+        let latest = diagnostic.measurement()?.time;
+        let mut values: Vec<f64> = diagnostic
+            .measurements()
+            .filter(|m| latest.duration_since(m.time) <= window && m.value.is_finite())
+            .map(|m| m.value)
+            .collect();
+        if values.is_empty() {
+            return None;
+        }
+        values.sort_by(f64::total_cmp);
+
+        let percentile = |p: f64| values[((values.len() - 1) as f64 * p).round() as usize];
+        Some(Self {
+            samples: values.len(),
+            average: values.iter().sum::<f64>() / values.len() as f64,
+            min: values[0],
+            max: values[values.len() - 1],
+            p50: percentile(0.50),
+            p95: percentile(0.95),
+            p99: percentile(0.99),
+        })
+    }
+}
+
+/// Add the frame time and entity count diagnostics if the game hasn't.
+///
+/// Runs from [`Plugin::finish`] so it sees plugins added after the inspector.
+pub(crate) fn add_missing_diagnostics_plugins(app: &mut App) {
+    let history_length = app
+        .world()
+        .get_resource::<DiagnosticsConfig>()
+        .map_or(DiagnosticsConfig::default().history_length, |config| {
+            config.history_length
+        });
+
+    if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+        app.add_plugins(FrameTimeDiagnosticsPlugin {
+            max_history_length: history_length,
+            // Seconds for the smoothed value to catch up with a change
+            smoothing_factor: 0.5,
+        });
+    }
+    if !app.is_plugin_added::<EntityCountDiagnosticsPlugin>() {
+        app.add_plugins(EntityCountDiagnosticsPlugin::new(history_length));
+    }
+}
 
 /// Render the diagnostics tab.
-pub fn render(ui: &mut egui::Ui, world: &World) {
-    ui.heading("Performance");
+pub fn render(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    selected_entities: &mut SelectedEntities,
+    selection: &mut InspectorSelection,
+) {
+    let config = world
+        .get_resource::<DiagnosticsConfig>()
+        .cloned()
+        .unwrap_or_default();
+    let DiagnosticsConfig {
+        mut window_secs,
+        pinned,
+        spike_threshold_ms,
+        ..
+    } = config;
+
+    let export_status_id = ui.id().with("export_status");
+    ui.horizontal(|ui| {
+        ui.heading("Performance");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.menu_button("Export", |ui| {
+                for (label, format) in [("CSV", ExportFormat::Csv), ("JSON", ExportFormat::Json)] {
+                    if ui.button(label).clicked() {
+                        let status = match export_diagnostics(world, format) {
+                            Ok(path) => format!("Saved to {}", path.display()),
+                            Err(err) => {
+                                warn!("{err}");
+                                err.to_string()
+                            }
+                        };
+                        ui.data_mut(|d| d.insert_temp(export_status_id, status));
+                        ui.close();
+                    }
+                }
+            })
+            .response
+            .on_hover_text("Write the recorded history to a file");
+
+            let changed = ui
+                .add(
+                    egui::DragValue::new(&mut window_secs)
+                        .range(1.0..=120.0)
+                        .speed(0.5)
+                        .suffix(" s"),
+                )
+                .on_hover_text("History shown in the graphs and used for the statistics")
+                .changed();
+            if changed && let Some(mut config) = world.get_resource_mut::<DiagnosticsConfig>() {
+                config.window_secs = window_secs;
+            }
+        });
+    });
+    if let Some(status) = ui.data(|d| d.get_temp::<String>(export_status_id)) {
+        ui.weak(status);
+    }
     ui.separator();
 
-    let Some(time) = world.get_resource::<Time>() else {
-        ui.label("Time resource not available");
+    let Some(store) = world.get_resource::<DiagnosticsStore>() else {
+        ui.label("DiagnosticsStore not available");
         return;
     };
-    let fps = 1.0 / time.delta_secs();
-    let frame_time = time.delta_secs() * 1000.0;
+    let window = Duration::from_secs_f32(window_secs);
+    let mut pin_toggles = Vec::new();
 
-    // Performance metrics in a grid
-    ui.columns(2, |columns| {
-        columns[0].label("FPS:");
-        columns[1].colored_label(
-            if fps >= 60.0 {
-                egui::Color32::GREEN
-            } else if fps >= 30.0 {
-                egui::Color32::YELLOW
-            } else {
-                egui::Color32::RED
-            },
-            format!("{fps:.1} Hz"),
-        );
+    if !pinned.is_empty() {
+        render_pinned(ui, store, &pinned, window, &mut pin_toggles);
+        ui.add_space(10.0);
+    }
 
-        columns[0].label("Frame Time:");
-        columns[1].colored_label(
-            if frame_time <= 16.7 {
-                egui::Color32::GREEN
-            } else if frame_time <= 33.3 {
-                egui::Color32::YELLOW
-            } else {
-                egui::Color32::RED
-            },
-            format!("{frame_time:.1} ms"),
-        );
-    });
+    if let Some(frame_time) = store.get(&FrameTimeDiagnosticsPlugin::FRAME_TIME) {
+        render_frame_time(ui, store, frame_time, window);
+    } else {
+        ui.label("Frame time diagnostics not available");
+    }
+
+    ui.add_space(10.0);
+    let spike_changes = world
+        .get_resource::<FrameSpikes>()
+        .map(|spikes| render_spikes(ui, spikes, spike_threshold_ms));
 
     ui.add_space(10.0);
     ui.heading("Entities");
     ui.separator();
 
-    // Entity counts
-    let total_entities = world.entities().len();
+    // Entity counts, leaving out the inspector's own entities
+    let counts = EntityCounts::collect(world);
 
     ui.columns(2, |columns| {
         columns[0].label("Total Entities:");
-        columns[1].label(format!("{total_entities}"));
+        columns[1].label(format!("{}", counts.game));
+
+        columns[0].label("Inspector Entities:");
+        columns[1].weak(format!("{}", counts.internal));
+    });
+
+    if let Some(entity_count) = store.get(&EntityCountDiagnosticsPlugin::ENTITY_COUNT) {
+        ui.add_space(4.0);
+        history_graph(ui, entity_count, window, &[]);
+    }
+    ui.add_space(4.0);
+    entity_stats::render(ui, world, selected_entities, selection);
+
+    ui.add_space(10.0);
+    ui.heading("All Diagnostics");
+    ui.separator();
+    render_all(ui, store, &pinned, window, &mut pin_toggles);
+
+    if let Some(SpikeChanges {
+        threshold_ms,
+        clear,
+    }) = spike_changes
+    {
+        if let Some(mut config) = world.get_resource_mut::<DiagnosticsConfig>() {
+            config.spike_threshold_ms = threshold_ms;
+        }
+        if clear {
+            world.resource_mut::<FrameSpikes>().clear();
+        }
+    }
+
+    if !pin_toggles.is_empty()
+        && let Some(mut config) = world.get_resource_mut::<DiagnosticsConfig>()
+    {
+        for path in pin_toggles {
+            config.toggle_pinned(&path);
+        }
+    }
+}
+
+/// Number of entities in the world, split into the game's and the inspector's own.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EntityCounts {
+    /// Entities not marked with [`InspectorInternal`].
+    pub game: u32,
+    /// Entities marked with [`InspectorInternal`].
+    pub internal: u32,
+}
+
+impl EntityCounts {
+    /// Count the entities in `world`.
+    pub fn collect(world: &World) -> Self {
+        let total = world.entities().len();
+        let internal = world
+            .try_query_filtered::<(), With<InspectorInternal>>()
+            .map_or(0, |mut query| query.iter(world).count() as u32);
+        Self {
+            game: total - internal,
+            internal,
+        }
+    }
+}
+
+/// Edits made in the Frame Spikes section, applied once the world is no longer borrowed.
+struct SpikeChanges {
+    threshold_ms: f64,
+    clear: bool,
+}
+
+fn render_spikes(ui: &mut egui::Ui, spikes: &FrameSpikes, threshold_ms: f64) -> SpikeChanges {
+    let mut changes = SpikeChanges {
+        threshold_ms,
+        clear: false,
+    };
+
+    ui.horizontal(|ui| {
+        ui.heading("Frame Spikes");
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            changes.clear = ui
+                .add_enabled(!spikes.is_empty(), egui::Button::new("Clear"))
+                .clicked();
+            ui.add(
+                egui::DragValue::new(&mut changes.threshold_ms)
+                    .range(1.0..=1000.0)
+                    .speed(0.5)
+                    .prefix("> ")
+                    .suffix(" ms"),
+            )
+            .on_hover_text("Frames slower than this are recorded");
+        });
+    });
+    ui.separator();
+
+    if spikes.is_empty() {
+        ui.weak(format!("No frames over {threshold_ms:.1} ms yet"));
+        return changes;
+    }
+
+    // Newest first
+    for spike in spikes.iter().rev() {
+        let header = egui::RichText::new(format!(
+            "Frame {}: {:.1} ms at {:.1} s",
+            spike.frame, spike.frame_time_ms, spike.elapsed_secs
+        ))
+        .color(frame_time_color(spike.frame_time_ms));
+        egui::CollapsingHeader::new(header)
+            .id_salt(("frame_spike", spike.frame))
+            .show(ui, |ui| {
+                ui.label(format!("Entities: {}", spike.entity_count));
+                egui::Grid::new(("frame_spike_diagnostics", spike.frame))
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for diagnostic in &spike.diagnostics {
+                            ui.label(&diagnostic.path);
+                            ui.monospace(format!("{:.2}{}", diagnostic.value, diagnostic.suffix));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    changes
+}
+
+fn render_pinned(
+    ui: &mut egui::Ui,
+    store: &DiagnosticsStore,
+    pinned: &[String],
+    window: Duration,
+    pin_toggles: &mut Vec<String>,
+) {
+    ui.heading("Pinned");
+    ui.separator();
+
+    for path in pinned {
+        let diagnostic = store.iter().find(|d| d.path().as_str() == path);
+        ui.horizontal(|ui| {
+            if pin_button(ui, true).clicked() {
+                pin_toggles.push(path.clone());
+            }
+            ui.strong(path);
+            match diagnostic {
+                Some(diagnostic) => {
+                    ui.label(format_latest(diagnostic));
+                }
+                None => {
+                    ui.weak("not registered");
+                }
+            }
+        });
+        if let Some(diagnostic) = diagnostic {
+            history_graph(ui, diagnostic, window, &[]);
+            ui.add_space(4.0);
+        }
+    }
+}
+
+/// Group diagnostics by their path up to the last `/`, in path order.
+///
+/// Diagnostics without a `/` in their path, such as Bevy's frame time and FPS,
+/// go in a "General" group that comes first.
+pub fn group_diagnostics<'a>(
+    diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+) -> Vec<(&'a str, Vec<&'a Diagnostic>)> {
+    let mut diagnostics: Vec<&Diagnostic> = diagnostics.into_iter().collect();
+    diagnostics.sort_by_key(|d| d.path().as_str());
+
+    let mut groups: Vec<(&str, Vec<&Diagnostic>)> = Vec::new();
+    for diagnostic in diagnostics {
+        let group = diagnostic
+            .path()
+            .as_str()
+            .rsplit_once('/')
+            .map_or(UNGROUPED, |(prefix, _)| prefix);
+        match groups.iter_mut().find(|(name, _)| *name == group) {
+            Some((_, members)) => members.push(diagnostic),
+            None => groups.push((group, vec![diagnostic])),
+        }
+    }
+    // Keep the ungrouped built-in diagnostics first
+    groups.sort_by_key(|(name, _)| (*name != UNGROUPED, *name));
+    groups
+}
+
+/// List every diagnostic in the store, grouped by the path up to its last `/`.
+fn render_all(
+    ui: &mut egui::Ui,
+    store: &DiagnosticsStore,
+    pinned: &[String],
+    window: Duration,
+    pin_toggles: &mut Vec<String>,
+) {
+    if store.iter().next().is_none() {
+        ui.weak("No diagnostics registered");
+        return;
+    }
+
+    for (group, members) in group_diagnostics(store.iter()) {
+        egui::CollapsingHeader::new(format!("{group} ({})", members.len()))
+            .id_salt(("diagnostics_group", group))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("diagnostics_grid", group))
+                    .num_columns(4)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for diagnostic in members {
+                            let path = diagnostic.path().as_str();
+                            let is_pinned = pinned.iter().any(|pinned| pinned == path);
+                            if pin_button(ui, is_pinned).clicked() {
+                                pin_toggles.push(path.to_string());
+                            }
+
+                            let name = path.rsplit_once('/').map_or(path, |(_, name)| name);
+                            let label = if diagnostic.is_enabled {
+                                ui.label(name)
+                            } else {
+                                ui.weak(format!("{name} (disabled)"))
+                            };
+                            label.on_hover_text(path);
+
+                            ui.monospace(format_latest(diagnostic));
+                            sparkline(ui, diagnostic, window);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+fn pin_button(ui: &mut egui::Ui, is_pinned: bool) -> egui::Response {
+    ui.selectable_label(is_pinned, "📌")
+        .on_hover_text(if is_pinned {
+            "Unpin"
+        } else {
+            "Pin to the top of the tab"
+        })
+}
+
+/// Latest and smoothed value of a diagnostic, with its unit.
+fn format_latest(diagnostic: &Diagnostic) -> String {
+    let suffix = &diagnostic.suffix;
+    match (diagnostic.value(), diagnostic.smoothed()) {
+        (Some(value), Some(smoothed)) if diagnostic.history_len() > 1 => {
+            format!("{value:.2}{suffix} (avg {smoothed:.2}{suffix})")
+        }
+        (Some(value), _) => format!("{value:.2}{suffix}"),
+        (None, _) => "-".to_string(),
+    }
+}
+
+fn render_frame_time(
+    ui: &mut egui::Ui,
+    store: &DiagnosticsStore,
+    frame_time: &Diagnostic,
+    window: Duration,
+) {
+    let fps = store
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(Diagnostic::smoothed);
+    let smoothed = frame_time.smoothed();
+    let stats = DiagnosticStats::over_window(frame_time, window);
+
+    ui.columns(2, |columns| {
+        columns[0].label("FPS:");
+        match fps {
+            Some(fps) => {
+                columns[1].colored_label(fps_color(fps), format!("{fps:.1} Hz"));
+            }
+            None => {
+                columns[1].weak("-");
+            }
+        }
+
+        columns[0].label("Frame Time:");
+        match smoothed {
+            Some(ms) => {
+                columns[1].colored_label(frame_time_color(ms), format!("{ms:.2} ms"));
+            }
+            None => {
+                columns[1].weak("-");
+            }
+        }
+
+        if let Some(stats) = stats {
+            columns[0].label("Min / Max:");
+            columns[1].label(format!("{:.2} / {:.2} ms", stats.min, stats.max));
+
+            let rows = [
+                ("Average:", stats.average),
+                ("p50:", stats.p50),
+                ("p95:", stats.p95),
+                ("p99:", stats.p99),
+            ];
+            for (label, value) in rows {
+                columns[0].label(label);
+                columns[1].colored_label(frame_time_color(value), format!("{value:.2} ms"));
+            }
+        }
     });
+
+    if let Some(stats) = stats {
+        ui.weak(format!(
+            "{} frames over the last {:.0} s",
+            stats.samples,
+            window.as_secs_f32()
+        ));
+    }
+
+    ui.add_space(4.0);
+    history_graph(ui, frame_time, window, &FRAME_BUDGETS_MS);
+}
+
+/// Draw a scrolling line graph of a diagnostic's measurements within `window`.
+///
+/// The newest measurement is at the right edge. `reference_lines` are drawn as
+/// faint horizontal lines and always fit in the graph.
+fn history_graph(
+    ui: &mut egui::Ui,
+    diagnostic: &Diagnostic,
+    window: Duration,
+    reference_lines: &[f64],
+) {
+    let (rect, response) =
+        ui.allocate_exact_size(egui::vec2(ui.available_width(), 80.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+
+    let window_secs = window.as_secs_f64().max(f64::EPSILON);
+    let samples = window_samples(diagnostic, window_secs);
+    let max_value = samples
+        .iter()
+        .map(|(_, value)| *value)
+        .chain(reference_lines.iter().copied())
+        .fold(0.0, f64::max)
+        * 1.1;
+    let range = 0.0..=if max_value > 0.0 { max_value } else { 1.0 };
+
+    let line_color = ui.visuals().weak_text_color();
+    for &reference in reference_lines {
+        let y = to_screen(rect, window_secs, &range, 0.0, reference).y;
+        painter.hline(
+            rect.x_range(),
+            y,
+            egui::Stroke::new(1.0, line_color.gamma_multiply(0.5)),
+        );
+    }
+
+    plot(
+        &painter,
+        rect,
+        &samples,
+        window_secs,
+        &range,
+        1.5,
+        ui.visuals().selection.bg_fill,
+    );
+
+    let suffix = &diagnostic.suffix;
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        format!("{:.1}{suffix}", range.end()),
+        egui::FontId::monospace(10.0),
+        line_color,
+    );
+
+    // Show the measurement under the cursor
+    if let Some(pointer) = response.hover_pos() {
+        let hovered_age = (rect.right() - pointer.x) as f64 / rect.width() as f64 * window_secs;
+        let nearest = samples.iter().min_by(|a, b| {
+            (a.0 - hovered_age)
+                .abs()
+                .total_cmp(&(b.0 - hovered_age).abs())
+        });
+        if let Some(&(age, value)) = nearest {
+            response.on_hover_text_at_pointer(format!("{value:.2}{suffix}  ({age:.1} s ago)"));
+        }
+    }
+}
+
+/// Draw a small inline graph scaled to the diagnostic's own range within `window`.
+fn sparkline(ui: &mut egui::Ui, diagnostic: &Diagnostic, window: Duration) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(120.0, 16.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 1.0, ui.visuals().extreme_bg_color);
+
+    let window_secs = window.as_secs_f64().max(f64::EPSILON);
+    let samples = window_samples(diagnostic, window_secs);
+    if samples.is_empty() {
+        return;
+    }
+    let (min, max) = samples
+        .iter()
+        .fold((f64::MAX, f64::MIN), |(min, max), (_, v)| {
+            (min.min(*v), max.max(*v))
+        });
+    // Flat lines sit in the middle instead of on the bottom edge
+    let padding = if max > min { 0.0 } else { 1.0 };
+    let range = (min - padding)..=(max + padding);
+
+    plot(
+        &painter,
+        rect,
+        &samples,
+        window_secs,
+        &range,
+        1.0,
+        ui.visuals().text_color(),
+    );
+}
+
+/// Measurements within `window_secs` of the latest one, as
+/// (seconds before the latest measurement, value).
+fn window_samples(diagnostic: &Diagnostic, window_secs: f64) -> Vec<(f64, f64)> {
+    let Some(latest) = diagnostic.measurement().map(|m| m.time) else {
+        return Vec::new();
+    };
+    diagnostic
+        .measurements()
+        .map(|m| (age(latest, m.time), m.value))
+        .filter(|(age, value)| *age <= window_secs && value.is_finite())
+        .collect()
+}
+
+fn plot(
+    painter: &egui::Painter,
+    rect: egui::Rect,
+    samples: &[(f64, f64)],
+    window_secs: f64,
+    range: &RangeInclusive<f64>,
+    width: f32,
+    color: egui::Color32,
+) {
+    let points: Vec<egui::Pos2> = samples
+        .iter()
+        .map(|&(age, value)| to_screen(rect, window_secs, range, age, value))
+        .collect();
+    painter.add(egui::Shape::line(points, egui::Stroke::new(width, color)));
+}
+
+fn to_screen(
+    rect: egui::Rect,
+    window_secs: f64,
+    range: &RangeInclusive<f64>,
+    age: f64,
+    value: f64,
+) -> egui::Pos2 {
+    let height = (range.end() - range.start()).max(f64::EPSILON);
+    egui::pos2(
+        rect.right() - (age / window_secs) as f32 * rect.width(),
+        rect.bottom() - ((value - range.start()) / height) as f32 * rect.height(),
+    )
+}
+
+fn age(latest: Instant, time: Instant) -> f64 {
+    latest.duration_since(time).as_secs_f64()
+}
+
+fn fps_color(fps: f64) -> egui::Color32 {
+    if fps >= 60.0 {
+        egui::Color32::GREEN
+    } else if fps >= 30.0 {
+        egui::Color32::YELLOW
+    } else {
+        egui::Color32::RED
+    }
+}
+
+fn frame_time_color(ms: f64) -> egui::Color32 {
+    if ms <= 16.7 {
+        egui::Color32::GREEN
+    } else if ms <= 33.3 {
+        egui::Color32::YELLOW
+    } else {
+        egui::Color32::RED
+    }
 }
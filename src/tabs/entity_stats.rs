@@ -0,0 +1,285 @@
+//! Per-archetype and per-component entity statistics for the Diagnostics tab.
+//!
+//! Used to track down entity leaks (a component count that keeps growing) and
+//! archetype fragmentation (many small archetypes with similar components).
+
+use bevy::{
+    ecs::{archetype::ArchetypeId, component::ComponentId},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_egui::egui;
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+
+use super::{Sort, SortColumn};
+use crate::{config::InspectorInternal, state::InspectorSelection};
+
+/// Entity count and layout of a single archetype.
+#[derive(Clone, Debug)]
+pub struct ArchetypeStats {
+    /// The archetype's id.
+    pub id: ArchetypeId,
+    /// Number of entities in the archetype.
+    pub entity_count: usize,
+    /// Short names of the archetype's components, sorted.
+    pub components: Vec<String>,
+    /// Combined size of the archetype's components for one entity, in bytes.
+    pub bytes_per_entity: usize,
+}
+
+/// Number of entities and archetypes that have a component.
+#[derive(Clone, Debug)]
+pub struct ComponentStats {
+    /// The component's id.
+    pub id: ComponentId,
+    /// Short name of the component type.
+    pub name: String,
+    /// Entities that have the component.
+    pub entity_count: usize,
+    /// Non-empty archetypes that contain the component.
+    pub archetype_count: usize,
+}
+
+/// Breakdown of the world's entities by archetype and by component.
+///
+/// Archetypes of entities marked with [`InspectorInternal`] are left out, as are
+/// archetypes with no entities.
+#[derive(Clone, Debug, Default)]
+pub struct EntityStats {
+    /// Non-empty archetypes, largest first.
+    pub archetypes: Vec<ArchetypeStats>,
+    /// Components present on at least one entity, most common first.
+    pub components: Vec<ComponentStats>,
+    /// Total number of archetypes in the world, including empty and internal ones.
+    pub total_archetypes: usize,
+}
+
+impl EntityStats {
+    /// Gather statistics from every archetype in `world`.
+    pub fn collect(world: &World) -> Self {
+        let components = world.components();
+        let internal = world.component_id::<InspectorInternal>();
+        let name_of = |id: ComponentId| {
+            components.get_info(id).map_or_else(
+                || format!("{id:?}"),
+                |info| info.name().shortname().to_string(),
+            )
+        };
+
+        let mut stats = EntityStats {
+            total_archetypes: world.archetypes().len(),
+            ..default()
+        };
+        let mut by_component: HashMap<ComponentId, ComponentStats> = HashMap::default();
+        for archetype in world.archetypes().iter() {
+            if archetype.is_empty() || internal.is_some_and(|id| archetype.contains(id)) {
+                continue;
+            }
+            let entity_count = archetype.len() as usize;
+
+            let mut names = Vec::with_capacity(archetype.component_count());
+            let mut bytes_per_entity = 0;
+            for id in archetype.iter_components() {
+                names.push(name_of(id));
+                bytes_per_entity += components
+                    .get_info(id)
+                    .map_or(0, |info| info.layout().size());
+
+                let component = by_component.entry(id).or_insert_with(|| ComponentStats {
+                    id,
+                    name: name_of(id),
+                    entity_count: 0,
+                    archetype_count: 0,
+                });
+                component.entity_count += entity_count;
+                component.archetype_count += 1;
+            }
+            names.sort();
+
+            stats.archetypes.push(ArchetypeStats {
+                id: archetype.id(),
+                entity_count,
+                components: names,
+                bytes_per_entity,
+            });
+        }
+
+        stats.components = by_component.into_values().collect();
+        stats
+            .archetypes
+            .sort_by_key(|a| std::cmp::Reverse(a.entity_count));
+        stats.components.sort_by(|a, b| {
+            b.entity_count
+                .cmp(&a.entity_count)
+                .then(a.name.cmp(&b.name))
+        });
+        stats
+    }
+
+    /// Entities in the archetype with the given id.
+    pub fn archetype_entities(world: &World, id: ArchetypeId) -> Vec<Entity> {
+        world
+            .archetypes()
+            .get(id)
+            .map(|archetype| archetype.entities().iter().map(|e| e.id()).collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Column the archetype and component tables are sorted by.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Column {
+    #[default]
+    Entities,
+    Name,
+    Size,
+}
+
+impl SortColumn for Column {
+    // Names read best A to Z, counts largest first
+    fn ascending_first(self) -> bool {
+        self == Column::Name
+    }
+}
+
+/// Render the archetype and component breakdown.
+pub fn render(
+    ui: &mut egui::Ui,
+    world: &World,
+    selected_entities: &mut SelectedEntities,
+    selection: &mut InspectorSelection,
+) {
+    // Walking every archetype is only worth it while a table is shown
+    let is_open = |salt: &str| {
+        egui::collapsing_header::CollapsingState::load(ui.ctx(), ui.make_persistent_id(salt))
+            .is_some_and(|state| state.is_open())
+    };
+    if !is_open("entity_stats_components") && !is_open("entity_stats_archetypes") {
+        egui::CollapsingHeader::new("Components")
+            .id_salt("entity_stats_components")
+            .show(ui, |_| {});
+        egui::CollapsingHeader::new("Archetypes")
+            .id_salt("entity_stats_archetypes")
+            .show(ui, |_| {});
+        return;
+    }
+
+    let mut stats = EntityStats::collect(world);
+    let mut to_select: Option<Vec<Entity>> = None;
+
+    egui::CollapsingHeader::new(format!("Components ({})", stats.components.len()))
+        .id_salt("entity_stats_components")
+        .show(ui, |ui| {
+            let sort_id = ui.id().with("sort");
+            let mut sort: Sort<Column> = ui.data(|d| d.get_temp(sort_id)).unwrap_or_default();
+            let components = &mut stats.components;
+            match sort.column {
+                Column::Name => components.sort_by(|a, b| a.name.cmp(&b.name)),
+                Column::Entities | Column::Size => {
+                    components.sort_by_key(|c| (c.entity_count, c.archetype_count));
+                }
+            }
+            if !sort.ascending {
+                components.reverse();
+            }
+
+            egui::Grid::new("entity_stats_components_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    sort.header(ui, "Component", Column::Name);
+                    sort.header(ui, "Entities", Column::Entities);
+                    ui.strong("Archetypes");
+                    ui.label("");
+                    ui.end_row();
+
+                    for component in components.iter() {
+                        ui.label(&component.name);
+                        ui.monospace(component.entity_count.to_string());
+                        ui.monospace(component.archetype_count.to_string());
+                        if ui.small_button("Select").clicked() {
+                            to_select = Some(component_entities(world, component.id));
+                        }
+                        ui.end_row();
+                    }
+                });
+            ui.data_mut(|d| d.insert_temp(sort_id, sort));
+        });
+
+    egui::CollapsingHeader::new(format!(
+        "Archetypes ({} non-empty of {})",
+        stats.archetypes.len(),
+        stats.total_archetypes
+    ))
+    .id_salt("entity_stats_archetypes")
+    .show(ui, |ui| {
+        let sort_id = ui.id().with("sort");
+        let mut sort: Sort<Column> = ui.data(|d| d.get_temp(sort_id)).unwrap_or_default();
+        let archetypes = &mut stats.archetypes;
+        match sort.column {
+            Column::Entities => archetypes.sort_by_key(|a| a.entity_count),
+            Column::Name => archetypes.sort_by_key(|a| a.components.len()),
+            Column::Size => archetypes.sort_by_key(|a| a.bytes_per_entity * a.entity_count),
+        }
+        if !sort.ascending {
+            archetypes.reverse();
+        }
+
+        egui::Grid::new("entity_stats_archetypes_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                sort.header(ui, "Components", Column::Name);
+                sort.header(ui, "Entities", Column::Entities);
+                sort.header(ui, "Size", Column::Size);
+                ui.label("");
+                ui.end_row();
+
+                for archetype in archetypes.iter() {
+                    ui.label(format!("{} components", archetype.components.len()))
+                        .on_hover_text(archetype.components.join("\n"));
+                    ui.monospace(archetype.entity_count.to_string());
+                    ui.monospace(format_bytes(
+                        archetype.bytes_per_entity * archetype.entity_count,
+                    ))
+                    .on_hover_text(format!("{} bytes per entity", archetype.bytes_per_entity));
+                    if ui.small_button("Select").clicked() {
+                        to_select = Some(EntityStats::archetype_entities(world, archetype.id));
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.data_mut(|d| d.insert_temp(sort_id, sort));
+    });
+
+    if let Some(entities) = to_select {
+        selected_entities.clear();
+        for entity in entities {
+            selected_entities.select_maybe_add(entity, true);
+        }
+        *selection = InspectorSelection::Entities;
+    }
+}
+
+/// Entities that have the given component, leaving out the inspector's own.
+fn component_entities(world: &World, id: ComponentId) -> Vec<Entity> {
+    let internal = world.component_id::<InspectorInternal>();
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| {
+            archetype.contains(id) && !internal.is_some_and(|id| archetype.contains(id))
+        })
+        .flat_map(|archetype| archetype.entities().iter().map(|e| e.id()))
+        .collect()
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} B")
+    }
+}
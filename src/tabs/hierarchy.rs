@@ -5,9 +5,13 @@
 use bevy::ecs::world::EntityRef;
 use bevy::prelude::*;
 use bevy_egui::egui;
-use bevy_inspector_egui::bevy_inspector::hierarchy::{SelectedEntities, hierarchy_ui};
+use bevy_inspector_egui::bevy_inspector::hierarchy::{
+    SelectedEntities, hierarchy_ui, hierarchy_ui_filtered,
+};
 
+use crate::config::InspectorInternal;
 use crate::state::InspectorSelection;
+use crate::undo;
 
 /// Render the hierarchy tab.
 pub fn render(
@@ -16,6 +20,7 @@ pub fn render(
     selected_entities: &mut SelectedEntities,
     selection: &mut InspectorSelection,
     hierarchy_search: &mut String,
+    show_internal_entities: &mut bool,
 ) {
     // Search input
     ui.horizontal(|ui| {
@@ -24,20 +29,41 @@ pub fn render(
         if ui.small_button("X").clicked() {
             hierarchy_search.clear();
         }
+        if ui
+            .small_button("+")
+            .on_hover_text("Spawn an empty entity")
+            .clicked()
+        {
+            let entity = undo::spawn_entity(world);
+            selected_entities.select_replace(entity);
+            *selection = InspectorSelection::Entities;
+        }
     });
+    ui.checkbox(show_internal_entities, "Show inspector internals");
     ui.separator();
 
     let search_query = hierarchy_search.trim().to_lowercase();
 
     if search_query.is_empty() {
         // No search - use default hierarchy UI
-        let selected = hierarchy_ui(world, ui, selected_entities);
+        let selected = if *show_internal_entities {
+            hierarchy_ui(world, ui, selected_entities)
+        } else {
+            hierarchy_ui_filtered::<Without<InspectorInternal>>(world, ui, selected_entities)
+        };
         if selected {
             *selection = InspectorSelection::Entities;
         }
     } else {
         // Filtered entity list based on search
-        render_filtered_hierarchy(ui, world, selected_entities, selection, &search_query);
+        render_filtered_hierarchy(
+            ui,
+            world,
+            selected_entities,
+            selection,
+            &search_query,
+            *show_internal_entities,
+        );
     }
 }
 
@@ -48,36 +74,10 @@ fn render_filtered_hierarchy(
     selected_entities: &mut SelectedEntities,
     selection: &mut InspectorSelection,
     search_query: &str,
+    show_internal_entities: bool,
 ) {
     egui::ScrollArea::vertical().show(ui, |ui| {
-        let mut matching_entities: Vec<(Entity, String)> = Vec::new();
-
-        // Search by Name component
-        let mut q_named = world.query::<(Entity, &Name)>();
-        for (entity, name) in q_named.iter(world) {
-            if name.as_str().to_lowercase().contains(search_query) {
-                matching_entities.push((entity, name.to_string()));
-            }
-        }
-
-        // Also search by Entity ID (e.g., "123v4")
-        let mut q_all = world.query::<EntityRef>();
-        for entity_ref in q_all.iter(world) {
-            let entity_id = entity_ref.id();
-            let id_str = format!("{}v{}", entity_id.index(), entity_id.generation());
-            if id_str.contains(search_query) {
-                let name = entity_ref
-                    .get::<Name>()
-                    .map(|n| n.to_string())
-                    .unwrap_or_else(|| id_str.clone());
-                if !matching_entities.iter().any(|(e, _)| *e == entity_id) {
-                    matching_entities.push((entity_id, name));
-                }
-            }
-        }
-
-        // Sort by name for consistent ordering
-        matching_entities.sort_by(|(_, a), (_, b)| a.cmp(b));
+        let matching_entities = search_entities(world, search_query, show_internal_entities);
 
         // Display results
         ui.label(format!("{} results", matching_entities.len()));
@@ -96,3 +96,50 @@ fn render_filtered_hierarchy(
         }
     });
 }
+
+/// Entities whose name or id (e.g. "123v4") contains `search_query`, sorted by
+/// display name.
+///
+/// `search_query` is expected in lowercase. Entities marked with
+/// [`InspectorInternal`] are left out unless `show_internal_entities` is set.
+pub fn search_entities(
+    world: &mut World,
+    search_query: &str,
+    show_internal_entities: bool,
+) -> Vec<(Entity, String)> {
+    let mut matching_entities: Vec<(Entity, String)> = Vec::new();
+
+    // Search by Name component
+    let mut q_named = world.query::<(Entity, &Name, Has<InspectorInternal>)>();
+    for (entity, name, internal) in q_named.iter(world) {
+        if internal && !show_internal_entities {
+            continue;
+        }
+        if name.as_str().to_lowercase().contains(search_query) {
+            matching_entities.push((entity, name.to_string()));
+        }
+    }
+
+    // Also search by Entity ID (e.g., "123v4")
+    let mut q_all = world.query::<EntityRef>();
+    for entity_ref in q_all.iter(world) {
+        if entity_ref.contains::<InspectorInternal>() && !show_internal_entities {
+            continue;
+        }
+        let entity_id = entity_ref.id();
+        let id_str = format!("{}v{}", entity_id.index(), entity_id.generation());
+        if id_str.contains(search_query) {
+            let name = entity_ref
+                .get::<Name>()
+                .map(|n| n.to_string())
+                .unwrap_or_else(|| id_str.clone());
+            if !matching_entities.iter().any(|(e, _)| *e == entity_id) {
+                matching_entities.push((entity_id, name));
+            }
+        }
+    }
+
+    // Sort by name for consistent ordering
+    matching_entities.sort_by(|(_, a), (_, b)| a.cmp(b));
+    matching_entities
+}
@@ -1,6 +1,7 @@
 //! Entity/resource/asset inspector tab.
 //!
-//! Displays detailed information about the currently selected item.
+//! Displays detailed information about the currently selected item. Edits are
+//! recorded in the [`UndoHistory`](crate::UndoHistory).
 
 use bevy::prelude::*;
 use bevy::reflect::TypeRegistry;
@@ -11,18 +12,58 @@ use bevy_inspector_egui::bevy_inspector::{
 };
 
 use crate::state::InspectorSelection;
+use crate::undo::{self, EditTarget, ValueSnapshot};
 
 /// Render the inspector tab.
 pub fn render(
     ui: &mut egui::Ui,
     world: &mut World,
     type_registry: &TypeRegistry,
-    selected_entities: &SelectedEntities,
+    selected_entities: &mut SelectedEntities,
     selection: &InspectorSelection,
 ) {
+    // Only copy the shown values on frames where the user can change them
+    let editing = owns_input(ui)
+        && ui.input(|input| {
+            input.pointer.any_down()
+                || input.pointer.any_released()
+                || input.events.iter().any(|event| {
+                    matches!(
+                        event,
+                        egui::Event::Key { .. } | egui::Event::Text(_) | egui::Event::Paste(_)
+                    )
+                })
+        });
+    let snapshot = editing.then(|| match selection {
+        InspectorSelection::Entities => {
+            let entities = selected_entities.as_slice();
+            ValueSnapshot::entities(world, entities, entities.len() == 1, type_registry)
+        }
+        InspectorSelection::Resource(type_id, _) => {
+            ValueSnapshot::value(world, EditTarget::Resource(*type_id), type_registry)
+        }
+        InspectorSelection::Asset(type_id, _, handle) => {
+            ValueSnapshot::value(world, EditTarget::Asset(*type_id, *handle), type_registry)
+        }
+    });
+
+    // Components the widgets write to are marked changed after this tick
+    let drawn_after = editing.then(|| world.increment_change_tick());
+
+    let mut despawn = None;
     match selection {
         InspectorSelection::Entities => match selected_entities.as_slice() {
-            &[entity] => ui_for_entity_with_children(world, entity, ui),
+            &[entity] => {
+                if world.get_entity(entity).is_ok()
+                    && ui
+                        .button("Despawn")
+                        .on_hover_text("Despawn this entity and its children")
+                        .clicked()
+                {
+                    despawn = Some(entity);
+                }
+                ui_for_entity_with_children(world, entity, ui);
+            }
             entities => ui_for_entities_shared_components(world, entities, ui),
         },
         InspectorSelection::Resource(type_id, name) => {
@@ -34,4 +75,31 @@ pub fn render(
             bevy_inspector::by_type_id::ui_for_asset(world, *type_id, *handle, ui, type_registry);
         }
     }
+
+    if let Some((snapshot, drawn_after)) = snapshot.zip(drawn_after) {
+        // Frames of one drag, or keystrokes into one field, become one edit
+        let gesture = ui.input(|input| input.pointer.press_start_time().map(f64::to_bits));
+        let gesture = gesture.or_else(|| ui.memory(|memory| memory.focused().map(|id| id.value())));
+        snapshot.record_changes(world, drawn_after, gesture, type_registry);
+    }
+    if let Some(entity) = despawn {
+        undo::despawn_entity(world, entity, type_registry);
+        selected_entities.remove(entity);
+    }
+}
+
+/// Whether the pointer is over the tab, or a widget in it is focused or being
+/// dragged.
+fn owns_input(ui: &egui::Ui) -> bool {
+    if ui.rect_contains_pointer(ui.max_rect()) {
+        return true;
+    }
+    let ctx = ui.ctx();
+    [ui.memory(|memory| memory.focused()), ctx.dragged_id()]
+        .into_iter()
+        .flatten()
+        .filter_map(|id| ctx.read_response(id))
+        .any(|response| {
+            response.layer_id == ui.layer_id() && ui.clip_rect().intersects(response.rect)
+        })
 }
@@ -0,0 +1,108 @@
+//! Lifecycle tab: components added to, replaced on and removed from the
+//! selected entities, frame by frame.
+
+use bevy::{ecs::component::ComponentId, platform::collections::HashMap, prelude::*};
+use bevy_egui::egui;
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+
+use crate::component_timeline::{ComponentChange, ComponentEvent, ComponentTimeline};
+
+/// Render the Lifecycle tab.
+pub fn render(ui: &mut egui::Ui, world: &mut World, selected_entities: &SelectedEntities) {
+    let Some(mut timeline) = world.get_resource_mut::<ComponentTimeline>() else {
+        ui.label("Component timeline is not available");
+        return;
+    };
+    if selected_entities.is_empty() {
+        ui.label("Select an entity to record its component changes.");
+        return;
+    }
+
+    let filter_id = ui.id().with("lifecycle_filter");
+    let mut filter: String = ui.data(|d| d.get_temp(filter_id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        if ui.button("Clear").clicked() {
+            timeline.clear();
+        }
+        ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Filter by component"));
+    });
+    let filter_lower = filter.to_lowercase();
+    ui.data_mut(|d| d.insert_temp(filter_id, filter));
+
+    let world: &World = world;
+    let timeline = world.resource::<ComponentTimeline>();
+    let events: Vec<&ComponentEvent> = timeline
+        .events()
+        .filter(|event| selected_entities.contains(event.entity))
+        .collect();
+    if events.is_empty() {
+        ui.weak("No component changes since the selection was made.");
+        return;
+    }
+
+    let show_entity = selected_entities.len() > 1;
+    let mut added_in: HashMap<(Entity, ComponentId), u32> = HashMap::default();
+    egui::ScrollArea::vertical()
+        .stick_to_bottom(true)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("lifecycle_grid")
+                .num_columns(if show_entity { 4 } else { 3 })
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Frame");
+                    if show_entity {
+                        ui.strong("Entity");
+                    }
+                    ui.strong("Change");
+                    ui.strong("Component");
+                    ui.end_row();
+
+                    for event in events {
+                        let key = (event.entity, event.component);
+                        let lifetime = match event.change {
+                            ComponentChange::Added => {
+                                added_in.insert(key, event.frame);
+                                None
+                            }
+                            ComponentChange::Replaced => None,
+                            ComponentChange::Removed => {
+                                added_in.remove(&key).map(|frame| event.frame - frame)
+                            }
+                        };
+                        if !filter_lower.is_empty()
+                            && !event.name.to_lowercase().contains(&filter_lower)
+                        {
+                            continue;
+                        }
+
+                        ui.monospace(event.frame.to_string());
+                        if show_entity {
+                            entity_label(ui, world, event.entity);
+                        }
+                        change_label(ui, event.change);
+                        let name = ui.label(&event.name);
+                        if let Some(frames) = lifetime {
+                            name.on_hover_text(format!("Present for {frames} frame(s)"));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+}
+
+fn change_label(ui: &mut egui::Ui, change: ComponentChange) {
+    let (text, color) = match change {
+        ComponentChange::Added => ("+ added", egui::Color32::GREEN),
+        ComponentChange::Replaced => ("~ replaced", egui::Color32::YELLOW),
+        ComponentChange::Removed => ("- removed", egui::Color32::RED),
+    };
+    ui.colored_label(color, text);
+}
+
+fn entity_label(ui: &mut egui::Ui, world: &World, entity: Entity) {
+    match world.get::<Name>(entity) {
+        Some(name) => ui.label(format!("{name} ({entity})")),
+        None => ui.label(entity.to_string()),
+    };
+}
@@ -0,0 +1,295 @@
+//! Messages tab: per-frame counts of every message type, a filterable log
+//! of recent payloads, and an editor for sending messages.
+
+use std::any::TypeId;
+
+use bevy::prelude::*;
+use bevy::reflect::{PartialReflect, TypeRegistry};
+use bevy_egui::egui;
+use bevy_inspector_egui::reflect_inspector::{ui_for_value, ui_for_value_readonly};
+
+use crate::{
+    message_monitor::{MessageMonitor, MessageStream},
+    message_sender::{self, MessageSender, ReflectSendMessage},
+    reflection::clone_reflected,
+};
+
+/// Render the Messages tab.
+pub fn render(ui: &mut egui::Ui, world: &mut World, type_registry: &TypeRegistry) {
+    if world.contains_resource::<MessageSender>() {
+        egui::CollapsingHeader::new("Send")
+            .id_salt("message_sender")
+            .show(ui, |ui| {
+                world.resource_scope(|world, mut sender: Mut<MessageSender>| {
+                    send_ui(ui, world, &mut sender, type_registry);
+                });
+            });
+        ui.separator();
+    }
+
+    let Some(mut monitor) = world.get_resource_mut::<MessageMonitor>() else {
+        ui.label("Message monitoring is not available");
+        return;
+    };
+    if monitor.streams().next().is_none() {
+        ui.label("No message types registered yet.");
+        return;
+    }
+
+    egui::ScrollArea::vertical()
+        .id_salt("message_streams")
+        .max_height(ui.available_height() * 0.4)
+        .auto_shrink([false, true])
+        .show(ui, |ui| stream_table(ui, &mut monitor));
+    ui.separator();
+
+    let filter_id = ui.id().with("message_filter");
+    let mut filter: String = ui.data(|d| d.get_temp(filter_id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        let (icon, action) = if monitor.is_paused() {
+            ("▶", "Resume logging")
+        } else {
+            ("⏸", "Pause logging")
+        };
+        if ui.button(icon).on_hover_text(action).clicked() {
+            let paused = monitor.is_paused();
+            monitor.set_paused(!paused);
+        }
+        if ui.button("Clear").clicked() {
+            monitor.clear_log();
+        }
+        ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Filter by type or payload"));
+        ui.weak(format!("{} logged", monitor.log().len()));
+    });
+    let filter_lower = filter.to_lowercase();
+    ui.data_mut(|d| d.insert_temp(filter_id, filter));
+
+    if !monitor.streams().any(|stream| stream.logging) {
+        ui.weak("Tick Log next to a message type to record its payloads.");
+        return;
+    }
+    egui::ScrollArea::vertical()
+        .id_salt("message_log")
+        .stick_to_bottom(true)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            let matches = monitor.log().filter(|message| {
+                filter_lower.is_empty()
+                    || message.stream.to_lowercase().contains(&filter_lower)
+                    || message.text.to_lowercase().contains(&filter_lower)
+            });
+            for message in matches {
+                let id = ui.make_persistent_id(("logged_message", &message.stream, message.id));
+                egui::collapsing_header::CollapsingState::load_with_default_open(
+                    ui.ctx(),
+                    id,
+                    false,
+                )
+                .show_header(ui, |ui| {
+                    ui.monospace(format!("#{}", message.frame));
+                    ui.strong(&message.stream);
+                    ui.add(egui::Label::new(egui::RichText::new(&message.text).weak()).truncate());
+                })
+                .body(|ui| {
+                    ui.push_id(id, |ui| {
+                        ui_for_value_readonly(message.value.as_ref(), ui, type_registry);
+                    });
+                });
+            }
+        });
+}
+
+/// One row per message type with its counts and a checkbox to log it.
+fn stream_table(ui: &mut egui::Ui, monitor: &mut MessageMonitor) {
+    let mut toggled = None;
+    egui::Grid::new("message_stream_grid")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Log");
+            ui.strong("Message");
+            ui.strong("Last frame");
+            ui.strong("Mean / frame");
+            ui.strong("Total");
+            ui.end_row();
+
+            for stream in monitor.streams() {
+                if !stream.reflected {
+                    unreflected_row(ui, stream);
+                    continue;
+                }
+                let mut logging = stream.logging;
+                if ui.add(egui::Checkbox::without_text(&mut logging)).changed() {
+                    toggled = Some((stream.name.clone(), logging));
+                }
+                ui.label(&stream.name);
+                ui.monospace(stream.last_frame().to_string());
+                ui.monospace(format!("{:.2}", stream.mean_per_frame()));
+                ui.monospace(stream.total.to_string());
+                ui.end_row();
+            }
+        });
+    if let Some((name, logging)) = toggled {
+        monitor.set_logging(&name, logging);
+    }
+}
+
+fn unreflected_row(ui: &mut egui::Ui, stream: &MessageStream) {
+    ui.add_enabled(false, egui::Checkbox::without_text(&mut false));
+    ui.weak(&stream.name).on_hover_text(format!(
+        "Register `Messages<{}>` for reflection to see counts and payloads",
+        stream.name
+    ));
+    for _ in 0..3 {
+        ui.weak("-");
+    }
+    ui.end_row();
+}
+
+/// Editor for one message value, and the list of saved macros.
+fn send_ui(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    sender: &mut MessageSender,
+    type_registry: &TypeRegistry,
+) {
+    let types = message_sender::sendable_types(type_registry);
+    if types.is_empty() {
+        ui.weak("No message types can be sent.");
+        ui.weak("Register them with `app.register_inspector_message::<DamageEvent>()`.");
+        return;
+    }
+
+    let current = sender
+        .draft
+        .as_ref()
+        .and_then(|draft| draft_type(draft.as_ref()));
+    let selected = types
+        .iter()
+        .find(|(_, type_id)| Some(*type_id) == current)
+        .map_or("Pick a message type…", |(name, _)| name);
+    let mut picked = None;
+    egui::ComboBox::from_id_salt("message_sender_type")
+        .selected_text(selected)
+        .show_ui(ui, |ui| {
+            for (name, type_id) in &types {
+                if ui
+                    .selectable_label(current == Some(*type_id), *name)
+                    .clicked()
+                {
+                    picked = Some(*type_id);
+                }
+            }
+        });
+    if let Some(send) = picked.and_then(|type_id| send_data(type_registry, type_id)) {
+        sender.draft = Some(send.default_value().into_partial_reflect());
+    }
+
+    let mut save_as = None;
+    if let Some(draft) = sender.draft.as_mut() {
+        ui.push_id("message_draft", |ui| {
+            ui_for_value(draft.as_mut(), ui, type_registry);
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Send").clicked() {
+                send(world, draft.as_ref(), type_registry);
+            }
+            ui.separator();
+            ui.add(
+                egui::TextEdit::singleline(&mut sender.new_macro_name)
+                    .hint_text("Macro name")
+                    .desired_width(120.0),
+            );
+            let name = sender.new_macro_name.trim();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save as macro"))
+                .clicked()
+            {
+                save_as = Some(name.to_string());
+            }
+        });
+    }
+    if let Some(name) = save_as
+        && let Some(draft) = sender.draft.as_deref().map(clone_reflected)
+    {
+        sender.add_macro(name, draft.as_ref());
+        sender.new_macro_name.clear();
+        save(sender, type_registry);
+    }
+
+    if sender.macros().next().is_none() {
+        return;
+    }
+    ui.add_space(4.0);
+    ui.strong("Macros");
+    let mut action = None;
+    egui::Grid::new("message_macro_grid")
+        .num_columns(4)
+        .striped(true)
+        .show(ui, |ui| {
+            for message_macro in sender.macros() {
+                ui.label(&message_macro.name);
+                ui.weak(message_macro.message_type());
+                if ui.button("Send").clicked() {
+                    send(world, message_macro.message.as_ref(), type_registry);
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("Edit")
+                        .on_hover_text("Load into the editor")
+                        .clicked()
+                    {
+                        action = Some(MacroAction::Edit(message_macro.name.clone()));
+                    }
+                    if ui.button("🗑").on_hover_text("Delete").clicked() {
+                        action = Some(MacroAction::Delete(message_macro.name.clone()));
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+    match action {
+        Some(MacroAction::Edit(name)) => {
+            if let Some(message_macro) = sender.get_macro(&name) {
+                sender.draft = Some(clone_reflected(message_macro.message.as_ref()));
+                sender.new_macro_name = name;
+            }
+        }
+        Some(MacroAction::Delete(name)) => {
+            sender.remove_macro(&name);
+            save(sender, type_registry);
+        }
+        None => {}
+    }
+}
+
+enum MacroAction {
+    Edit(String),
+    Delete(String),
+}
+
+fn draft_type(draft: &dyn PartialReflect) -> Option<TypeId> {
+    draft.get_represented_type_info().map(|info| info.type_id())
+}
+
+fn send_data(type_registry: &TypeRegistry, type_id: TypeId) -> Option<ReflectSendMessage> {
+    type_registry
+        .get_type_data::<ReflectSendMessage>(type_id)
+        .cloned()
+}
+
+fn send(world: &mut World, message: &dyn PartialReflect, type_registry: &TypeRegistry) {
+    let sent = draft_type(message)
+        .and_then(|type_id| send_data(type_registry, type_id))
+        .is_some_and(|send| send.write(world, message));
+    if !sent {
+        warn!("Cannot send message: its type has no `ReflectSendMessage`");
+    }
+}
+
+fn save(sender: &MessageSender, type_registry: &TypeRegistry) {
+    if let Err(err) = sender.save(type_registry) {
+        warn!("Failed to save message macros: {err}");
+    }
+}
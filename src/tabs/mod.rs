@@ -1,17 +1,56 @@
 //! Tab definitions and registration for the inspector.
 
+#[cfg(feature = "inspector")]
 mod assets;
-mod diagnostics;
+#[cfg(feature = "inspector")]
+pub(crate) mod diagnostics;
+#[cfg(feature = "inspector")]
+mod entity_stats;
+#[cfg(feature = "inspector")]
 mod game_view;
-mod hierarchy;
+#[cfg(feature = "inspector")]
+pub(crate) mod hierarchy;
+#[cfg(feature = "inspector")]
 mod inspector;
+#[cfg(feature = "inspector")]
+mod lifecycle;
+#[cfg(feature = "inspector")]
+mod messages;
+#[cfg(feature = "inspector")]
+mod observers;
+#[cfg(feature = "inspector")]
 mod resources;
-
-use bevy::prelude::*;
-use bevy_egui::egui;
+#[cfg(feature = "inspector")]
+mod schedules;
+#[cfg(feature = "inspector")]
+mod states;
+#[cfg(feature = "inspector")]
+mod systems;
+
+use bevy::{
+    prelude::*,
+    reflect::{GetTypeRegistration, Typed},
+};
+#[cfg(feature = "inspector")]
 use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
-
-use crate::state::InspectorSelection;
+#[cfg(feature = "inspector")]
+use serde::{Deserialize, Serialize};
+
+use crate::egui;
+#[cfg(feature = "inspector")]
+use crate::{
+    config::InspectorInternal,
+    message_sender::ReflectSendMessage,
+    observer_trace::{trace_entity_trigger, trace_trigger},
+    state::{InspectorSelection, UiState},
+};
+
+#[cfg(feature = "inspector")]
+pub(crate) use diagnostics::add_missing_diagnostics_plugins;
+#[cfg(feature = "inspector")]
+pub use diagnostics::{DiagnosticStats, group_diagnostics};
+#[cfg(feature = "inspector")]
+pub use entity_stats::{ArchetypeStats, ComponentStats, EntityStats};
 
 /// Trait for registering custom dev panel tabs.
 ///
@@ -65,6 +104,9 @@ use crate::state::InspectorSelection;
 /// ```
 pub trait InspectorTab: Send + Sync + 'static {
     /// Unique identifier for this tab.
+    ///
+    /// Used to refer to the tab from [`Tab::Custom`] and in saved layouts, so it
+    /// should stay the same across runs.
     fn id(&self) -> &'static str;
 
     /// Display name shown in tab header.
@@ -95,39 +137,186 @@ pub enum DockPosition {
 }
 
 /// Resource for registering custom tabs.
+///
+/// This is a queue of tabs waiting to be added to the dock. The inspector moves
+/// them into [`UiState`](crate::UiState) at Startup and whenever new tabs are
+/// registered later, so after that point the registry is empty and the open tabs
+/// are listed by [`UiState::custom_tab_ids`](crate::UiState::custom_tab_ids).
+#[cfg(feature = "inspector")]
 #[derive(Resource, Default)]
 pub struct InspectorTabRegistry {
     pub(crate) tabs: Vec<Box<dyn InspectorTab>>,
 }
 
+#[cfg(feature = "inspector")]
 impl InspectorTabRegistry {
     /// Register a custom tab.
+    ///
+    /// Tabs are identified by [`InspectorTab::id`]. If a tab with the same id is
+    /// already registered, a warning is logged and the new tab is ignored.
+    /// Use [`try_register`](Self::try_register) to handle duplicates yourself.
     pub fn register<T: InspectorTab>(&mut self, tab: T) {
+        if let Err(err) = self.try_register(tab) {
+            warn!("{err}; ignoring the later registration");
+        }
+    }
+
+    /// Register a custom tab, failing if its id is already taken.
+    pub fn try_register<T: InspectorTab>(&mut self, tab: T) -> Result<(), DuplicateTabId> {
+        if self.contains(tab.id()) {
+            return Err(DuplicateTabId(tab.id()));
+        }
         self.tabs.push(Box::new(tab));
+        Ok(())
+    }
+
+    /// Whether a tab with the given id is registered.
+    pub fn contains(&self, id: &str) -> bool {
+        self.tabs.iter().any(|tab| tab.id() == id)
     }
 
-    /// Get all registered tabs.
+    /// Remove a pending tab by id, returning it if it was registered.
+    pub fn remove(&mut self, id: &str) -> Option<Box<dyn InspectorTab>> {
+        let index = self.tabs.iter().position(|tab| tab.id() == id)?;
+        Some(self.tabs.remove(index))
+    }
+
+    /// Get all pending tabs.
     pub fn tabs(&self) -> &[Box<dyn InspectorTab>] {
         &self.tabs
     }
 
-    /// Get mutable access to all registered tabs.
+    /// Get mutable access to all pending tabs.
     pub fn tabs_mut(&mut self) -> &mut [Box<dyn InspectorTab>] {
         &mut self.tabs
     }
 
-    /// Number of registered custom tabs.
+    /// Number of tabs waiting to be added to the dock.
     pub fn len(&self) -> usize {
         self.tabs.len()
     }
 
-    /// Whether there are any registered custom tabs.
+    /// Whether there are no tabs waiting to be added to the dock.
     pub fn is_empty(&self) -> bool {
         self.tabs.is_empty()
     }
 }
 
+/// Extension trait for [`Commands`] to add and remove inspector tabs at runtime.
+///
+/// Tabs added this way are placed at their preferred [`DockPosition`], and removed
+/// tabs disappear from the dock. Commands issued before the inspector has started
+/// are queued in the `InspectorTabRegistry`. Without the `inspector` feature the
+/// commands do nothing.
+///
+/// # Example
+///
+/// ```ignore
+/// app.add_systems(OnEnter(GameState::Level), |mut commands: Commands| {
+///     commands.add_inspector_tab(LevelStatsTab::default());
+/// });
+/// app.add_systems(OnExit(GameState::Level), |mut commands: Commands| {
+///     commands.remove_inspector_tab("level_stats");
+/// });
+/// ```
+pub trait InspectorCommandsExt {
+    /// Add a custom tab to the dock. Duplicate ids are reported and ignored.
+    fn add_inspector_tab<T: InspectorTab>(&mut self, tab: T);
+
+    /// Remove the custom tab with the given id from the dock.
+    ///
+    /// Takes the same ids as [`Tab::custom`], so ids built at runtime work too.
+    fn remove_inspector_tab(&mut self, id: impl Into<String>);
+
+    /// Swap the custom tab that has the same id as `tab`, keeping its place in the dock.
+    ///
+    /// Adds the tab if no tab with that id exists yet.
+    fn replace_inspector_tab<T: InspectorTab>(&mut self, tab: T);
+}
+
+#[cfg_attr(not(feature = "inspector"), allow(unused_variables))]
+impl InspectorCommandsExt for Commands<'_, '_> {
+    fn add_inspector_tab<T: InspectorTab>(&mut self, tab: T) {
+        #[cfg(feature = "inspector")]
+        self.queue(move |world: &mut World| {
+            world.resource_mut::<InspectorTabRegistry>().register(tab);
+        });
+    }
+
+    fn remove_inspector_tab(&mut self, id: impl Into<String>) {
+        let id: String = id.into();
+        #[cfg(feature = "inspector")]
+        self.queue(move |world: &mut World| {
+            let removed_pending = world
+                .resource_mut::<InspectorTabRegistry>()
+                .remove(&id)
+                .is_some();
+            let removed_open = world
+                .get_resource_mut::<UiState>()
+                .and_then(|mut ui_state| ui_state.remove_custom_tab(&id))
+                .is_some();
+            if !removed_pending && !removed_open {
+                warn!("Cannot remove inspector tab \"{id}\": no tab with that id is registered");
+            }
+        });
+    }
+
+    fn replace_inspector_tab<T: InspectorTab>(&mut self, tab: T) {
+        #[cfg(feature = "inspector")]
+        self.queue(move |world: &mut World| {
+            let mut registry = world.resource_mut::<InspectorTabRegistry>();
+            if registry.remove(tab.id()).is_some() {
+                registry.register(tab);
+                return;
+            }
+            match world.get_resource_mut::<UiState>() {
+                Some(mut ui_state) => {
+                    ui_state.replace_custom_tab(Box::new(tab));
+                }
+                None => world.resource_mut::<InspectorTabRegistry>().register(tab),
+            }
+        });
+    }
+}
+
+/// System that moves tabs registered after Startup into the dock.
+#[cfg(feature = "inspector")]
+pub fn apply_pending_tabs(
+    mut registry: ResMut<InspectorTabRegistry>,
+    ui_state: Option<ResMut<UiState>>,
+) {
+    if registry.is_empty() {
+        return;
+    }
+    let Some(mut ui_state) = ui_state else {
+        return;
+    };
+
+    for tab in std::mem::take(&mut registry.tabs) {
+        if let Err(err) = ui_state.add_custom_tab(tab) {
+            warn!("{err}; ignoring the later registration");
+        }
+    }
+}
+
+/// Error returned when a custom tab is registered with an id that is already in use.
+#[cfg(feature = "inspector")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DuplicateTabId(pub &'static str);
+
+#[cfg(feature = "inspector")]
+impl std::fmt::Display for DuplicateTabId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "an inspector tab with id \"{}\" is already registered", self.0)
+    }
+}
+
+#[cfg(feature = "inspector")]
+impl std::error::Error for DuplicateTabId {}
+
 /// Extension trait for App to register inspector tabs.
+///
+/// Without the `inspector` feature the registrations are ignored.
 pub trait InspectorExt {
     /// Register a custom tab with full InspectorTab implementation.
     fn register_inspector_tab<T: InspectorTab>(&mut self, tab: T) -> &mut Self;
@@ -207,10 +396,55 @@ pub trait InspectorExt {
     ) -> &mut Self
     where
         F: FnMut(&mut egui::Ui, &mut World) + Send + Sync + 'static;
+
+    /// Let the Messages tab build and send messages of type `M`, and count and
+    /// log them.
+    ///
+    /// Registers `M` and `Messages<M>` for reflection and adds
+    /// [`ReflectSendMessage`] to `M`.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(Message, Reflect, Default)]
+    /// struct DamageEvent {
+    ///     amount: u32,
+    /// }
+    ///
+    /// app.add_message::<DamageEvent>()
+    ///     .register_inspector_message::<DamageEvent>();
+    /// ```
+    fn register_inspector_message<M>(&mut self) -> &mut Self
+    where
+        M: Message + Default + FromReflect + Typed + TypePath + GetTypeRegistration;
+
+    /// Record every trigger of the event `E` and the observers registered for it
+    /// in the Observers tab.
+    ///
+    /// Use [`trace_inspector_entity_event`](Self::trace_inspector_entity_event)
+    /// for [`EntityEvent`]s to also record their targets.
+    fn trace_inspector_event<E: Event>(&mut self) -> &mut Self;
+
+    /// Record every trigger of the entity event `E`, its target and the
+    /// observers registered for it in the Observers tab.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(EntityEvent)]
+    /// struct Explode {
+    ///     entity: Entity,
+    /// }
+    ///
+    /// app.trace_inspector_entity_event::<Explode>();
+    /// ```
+    fn trace_inspector_entity_event<E: EntityEvent>(&mut self) -> &mut Self;
 }
 
+#[cfg_attr(not(feature = "inspector"), allow(unused_variables))]
 impl InspectorExt for App {
     fn register_inspector_tab<T: InspectorTab>(&mut self, tab: T) -> &mut Self {
+        #[cfg(feature = "inspector")]
         self.world_mut()
             .resource_mut::<InspectorTabRegistry>()
             .register(tab);
@@ -276,6 +510,37 @@ impl InspectorExt for App {
             dock_position,
         })
     }
+
+    fn register_inspector_message<M>(&mut self) -> &mut Self
+    where
+        M: Message + Default + FromReflect + Typed + TypePath + GetTypeRegistration,
+    {
+        #[cfg(feature = "inspector")]
+        self.register_type::<M>()
+            .register_type::<Messages<M>>()
+            .register_type_data::<M, ReflectSendMessage>();
+        self
+    }
+
+    fn trace_inspector_event<E: Event>(&mut self) -> &mut Self {
+        #[cfg(feature = "inspector")]
+        self.world_mut().spawn((
+            Observer::new(trace_trigger::<E>),
+            InspectorInternal,
+            Name::new(format!("Trace {}", ShortName::of::<E>())),
+        ));
+        self
+    }
+
+    fn trace_inspector_entity_event<E: EntityEvent>(&mut self) -> &mut Self {
+        #[cfg(feature = "inspector")]
+        self.world_mut().spawn((
+            Observer::new(trace_entity_trigger::<E>),
+            InspectorInternal,
+            Name::new(format!("Trace {}", ShortName::of::<E>())),
+        ));
+        self
+    }
 }
 
 /// Wrapper for read-only analytics tabs using closures.
@@ -344,11 +609,19 @@ where
 
 /// Unified tab type that can represent both built-in and custom tabs.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "inspector", derive(Serialize, Deserialize))]
 pub enum Tab {
     /// A built-in tab provided by the inspector framework.
     Builtin(BuiltinTab),
-    /// A custom tab registered by the game, identified by index in the registry.
-    Custom(usize),
+    /// A custom tab registered by the game, identified by its [`InspectorTab::id`].
+    Custom(String),
+}
+
+impl Tab {
+    /// Refer to a custom tab by its [`InspectorTab::id`].
+    pub fn custom(id: impl Into<String>) -> Self {
+        Tab::Custom(id.into())
+    }
 }
 
 impl From<BuiltinTab> for Tab {
@@ -359,6 +632,7 @@ impl From<BuiltinTab> for Tab {
 
 /// Built-in tabs provided by the inspector framework.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "inspector", derive(Serialize, Deserialize))]
 pub enum BuiltinTab {
     /// The game viewport.
     GameView,
@@ -372,18 +646,131 @@ pub enum BuiltinTab {
     Assets,
     /// Performance diagnostics.
     Diagnostics,
+    /// Per-system run times.
+    Systems,
+    /// Schedule, system set and system browser.
+    Schedules,
+    /// Reflected `States` and their transitions.
+    States,
+    /// Message counts and a log of message payloads.
+    Messages,
+    /// Traced event triggers and the observers registered for them.
+    Observers,
+    /// Component additions, replacements and removals on the selected entities.
+    Lifecycle,
+}
+
+impl BuiltinTab {
+    /// Every built-in tab, in menu order.
+    pub const ALL: [BuiltinTab; 12] = [
+        BuiltinTab::GameView,
+        BuiltinTab::Hierarchy,
+        BuiltinTab::Inspector,
+        BuiltinTab::Resources,
+        BuiltinTab::Assets,
+        BuiltinTab::Diagnostics,
+        BuiltinTab::Systems,
+        BuiltinTab::Schedules,
+        BuiltinTab::States,
+        BuiltinTab::Messages,
+        BuiltinTab::Observers,
+        BuiltinTab::Lifecycle,
+    ];
+
+    /// Display name shown in the tab header and menus.
+    pub fn title(self) -> &'static str {
+        match self {
+            BuiltinTab::GameView => "Game",
+            BuiltinTab::Hierarchy => "Hierarchy",
+            BuiltinTab::Inspector => "Inspector",
+            BuiltinTab::Resources => "Resources",
+            BuiltinTab::Assets => "Assets",
+            BuiltinTab::Diagnostics => "Diagnostics",
+            BuiltinTab::Systems => "Systems",
+            BuiltinTab::Schedules => "Schedules",
+            BuiltinTab::States => "States",
+            BuiltinTab::Messages => "Messages",
+            BuiltinTab::Observers => "Observers",
+            BuiltinTab::Lifecycle => "Lifecycle",
+        }
+    }
+
+    /// Panel the tab lives in in the default layout.
+    pub fn dock_position(self) -> DockPosition {
+        match self {
+            BuiltinTab::GameView => DockPosition::Center,
+            BuiltinTab::Hierarchy
+            | BuiltinTab::Resources
+            | BuiltinTab::Diagnostics
+            | BuiltinTab::Schedules => DockPosition::Left,
+            BuiltinTab::Inspector => DockPosition::Right,
+            BuiltinTab::Assets
+            | BuiltinTab::Systems
+            | BuiltinTab::States
+            | BuiltinTab::Messages
+            | BuiltinTab::Observers
+            | BuiltinTab::Lifecycle => DockPosition::Bottom,
+        }
+    }
+}
+
+/// A column of a table that can be sorted by clicking its header.
+#[cfg(feature = "inspector")]
+pub(crate) trait SortColumn: Copy + PartialEq {
+    /// Whether sorting by this column starts in ascending order.
+    fn ascending_first(self) -> bool;
+}
+
+/// Sort state of a table, kept in egui memory.
+#[cfg(feature = "inspector")]
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Sort<C> {
+    pub(crate) column: C,
+    pub(crate) ascending: bool,
+}
+
+#[cfg(feature = "inspector")]
+impl<C: SortColumn> Sort<C> {
+    /// Header of `column`, which sorts by it when clicked, or flips the order
+    /// if the table is already sorted by it.
+    pub(crate) fn header(&mut self, ui: &mut egui::Ui, label: &str, column: C) {
+        let arrow = match (self.column == column, self.ascending) {
+            (false, _) => "",
+            (true, true) => " ⏶",
+            (true, false) => " ⏷",
+        };
+        if ui
+            .add(
+                egui::Button::new(egui::RichText::new(format!("{label}{arrow}")).strong())
+                    .frame(false),
+            )
+            .clicked()
+        {
+            if self.column == column {
+                self.ascending = !self.ascending;
+            } else {
+                *self = Sort {
+                    column,
+                    ascending: column.ascending_first(),
+                };
+            }
+        }
+    }
 }
 
 /// Tab viewer for egui_dock that handles both built-in and custom tabs.
+#[cfg(feature = "inspector")]
 pub struct TabViewer<'a> {
     pub world: &'a mut World,
     pub selected_entities: &'a mut SelectedEntities,
     pub selection: &'a mut InspectorSelection,
     pub viewport_rect: &'a mut egui::Rect,
     pub hierarchy_search: &'a mut String,
+    pub show_internal_entities: &'a mut bool,
     pub custom_tabs: &'a mut [Box<dyn InspectorTab>],
 }
 
+#[cfg(feature = "inspector")]
 impl egui_dock::TabViewer for TabViewer<'_> {
     type Tab = Tab;
 
@@ -408,6 +795,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                             self.selected_entities,
                             self.selection,
                             self.hierarchy_search,
+                            self.show_internal_entities,
                         );
                     }
                     BuiltinTab::Inspector => {
@@ -426,12 +814,40 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         assets::render(ui, &type_registry, self.world, self.selection);
                     }
                     BuiltinTab::Diagnostics => {
-                        diagnostics::render(ui, self.world);
+                        diagnostics::render(
+                            ui,
+                            self.world,
+                            self.selected_entities,
+                            self.selection,
+                        );
+                    }
+                    BuiltinTab::Systems => {
+                        systems::render(ui, self.world);
+                    }
+                    BuiltinTab::Schedules => {
+                        schedules::render(ui, self.world);
+                    }
+                    BuiltinTab::States => {
+                        states::render(ui, self.world);
+                    }
+                    BuiltinTab::Messages => {
+                        messages::render(ui, self.world, &type_registry);
+                    }
+                    BuiltinTab::Lifecycle => {
+                        lifecycle::render(ui, self.world, self.selected_entities);
+                    }
+                    BuiltinTab::Observers => {
+                        observers::render(
+                            ui,
+                            self.world,
+                            self.selected_entities,
+                            self.selection,
+                        );
                     }
                 }
             }
-            Tab::Custom(index) => {
-                if let Some(tab) = self.custom_tabs.get_mut(*index) {
+            Tab::Custom(id) => {
+                if let Some(tab) = self.custom_tabs.iter_mut().find(|tab| tab.id() == id) {
                     // Check visibility before rendering
                     // Note: We reborrow world as shared reference for the visibility check
                     let is_visible = {
@@ -447,7 +863,7 @@ impl egui_dock::TabViewer for TabViewer<'_> {
                         });
                     }
                 } else {
-                    ui.label(format!("Custom tab {} not found", index));
+                    ui.label(format!("Custom tab \"{id}\" not found"));
                 }
             }
         }
@@ -455,19 +871,12 @@ impl egui_dock::TabViewer for TabViewer<'_> {
 
     fn title(&mut self, window: &mut Self::Tab) -> egui::WidgetText {
         match window {
-            Tab::Builtin(builtin) => match builtin {
-                BuiltinTab::GameView => "Game".into(),
-                BuiltinTab::Hierarchy => "Hierarchy".into(),
-                BuiltinTab::Inspector => "Inspector".into(),
-                BuiltinTab::Resources => "Resources".into(),
-                BuiltinTab::Assets => "Assets".into(),
-                BuiltinTab::Diagnostics => "Diagnostics".into(),
-            },
-            Tab::Custom(index) => {
-                if let Some(tab) = self.custom_tabs.get(*index) {
+            Tab::Builtin(builtin) => builtin.title().into(),
+            Tab::Custom(id) => {
+                if let Some(tab) = self.custom_tabs.iter().find(|tab| tab.id() == id) {
                     tab.title().into()
                 } else {
-                    format!("Tab {}", index).into()
+                    id.as_str().into()
                 }
             }
         }
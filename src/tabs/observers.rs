@@ -0,0 +1,136 @@
+//! Observers tab: a filterable log of traced event triggers, their targets and
+//! the observers registered for them.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+use bevy_inspector_egui::bevy_inspector::hierarchy::SelectedEntities;
+
+use crate::observer_trace::{ObserverTrace, TriggerRecord};
+use crate::state::InspectorSelection;
+
+/// Render the Observers tab.
+pub fn render(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    selected_entities: &mut SelectedEntities,
+    selection: &mut InspectorSelection,
+) {
+    let Some(mut trace) = world.get_resource_mut::<ObserverTrace>() else {
+        ui.label("Observer tracing is not available");
+        return;
+    };
+
+    let filter_id = ui.id().with("observer_filter");
+    let mut filter: String = ui.data(|d| d.get_temp(filter_id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        let (icon, action) = if trace.is_paused() {
+            ("▶", "Resume tracing")
+        } else {
+            ("⏸", "Pause tracing")
+        };
+        if ui.button(icon).on_hover_text(action).clicked() {
+            let paused = trace.is_paused();
+            trace.set_paused(!paused);
+        }
+        if ui.button("Clear").clicked() {
+            trace.clear();
+        }
+        ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Filter by event or observer"));
+        ui.weak(format!("{} recorded", trace.records().len()));
+    });
+    let filter_lower = filter.to_lowercase();
+    ui.data_mut(|d| d.insert_temp(filter_id, filter));
+
+    let world: &World = world;
+    let trace = world.resource::<ObserverTrace>();
+    if trace.records().len() == 0 {
+        ui.weak("No triggers recorded yet.");
+        ui.weak("Trace events with `app.trace_inspector_entity_event::<Explode>()`.");
+        return;
+    }
+
+    let mut clicked = None;
+    egui::ScrollArea::vertical()
+        .stick_to_bottom(true)
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            egui::Grid::new("observer_trace_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("Frame");
+                    ui.strong("Event");
+                    ui.strong("Target");
+                    ui.strong("Registered observers");
+                    ui.end_row();
+
+                    let matches = trace
+                        .records()
+                        .filter(|record| matches_filter(record, &filter_lower));
+                    for record in matches {
+                        ui.monospace(record.frame.to_string());
+                        ui.strong(&record.event);
+                        match record.target {
+                            Some(target) => {
+                                if entity_link(ui, world, target, selected_entities) {
+                                    clicked = Some(target);
+                                }
+                            }
+                            None => {
+                                ui.weak("-");
+                            }
+                        }
+                        ui.horizontal_wrapped(|ui| {
+                            if record.observers.is_empty() {
+                                ui.weak("none");
+                            }
+                            for observer in &record.observers {
+                                let selected = selected_entities.contains(observer.entity);
+                                if ui
+                                    .selectable_label(selected, &observer.name)
+                                    .on_hover_text(format!("Observer {}", observer.entity))
+                                    .clicked()
+                                {
+                                    clicked = Some(observer.entity);
+                                }
+                            }
+                        });
+                        ui.end_row();
+                    }
+                });
+        });
+
+    if let Some(entity) = clicked {
+        let modifiers = ui.input(|i| i.modifiers);
+        selected_entities.select_maybe_add(entity, modifiers.ctrl || modifiers.shift);
+        *selection = InspectorSelection::Entities;
+    }
+}
+
+fn matches_filter(record: &TriggerRecord, filter_lower: &str) -> bool {
+    filter_lower.is_empty()
+        || record.event.to_lowercase().contains(filter_lower)
+        || record
+            .observers
+            .iter()
+            .any(|observer| observer.name.to_lowercase().contains(filter_lower))
+}
+
+/// Selectable label for a target entity. Returns true when clicked.
+fn entity_link(
+    ui: &mut egui::Ui,
+    world: &World,
+    entity: Entity,
+    selected_entities: &SelectedEntities,
+) -> bool {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        ui.weak(format!("{entity} (despawned)"));
+        return false;
+    };
+    let label = match entity_ref.get::<Name>() {
+        Some(name) => format!("{name} ({entity})"),
+        None => entity.to_string(),
+    };
+    ui.selectable_label(selected_entities.contains(entity), label)
+        .clicked()
+}
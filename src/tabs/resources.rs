@@ -24,7 +24,7 @@ pub fn render(
             )
         })
         .collect();
-    resources.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+    resources.sort_by_key(|(name, _)| *name);
 
     for (resource_name, type_id) in resources {
         let selected = match *selection {
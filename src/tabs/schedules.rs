@@ -0,0 +1,371 @@
+//! Schedules tab: every schedule's systems and sets as a searchable tree, with
+//! checkboxes to turn them off.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::{
+    schedule_graph::{ScheduleGraphs, ScheduleInfo, SystemAccess, SystemInfo},
+    system_toggle::{is_inspector_system, DisabledSystems},
+};
+
+/// Search text and selected system, kept in egui memory.
+#[derive(Clone, Default)]
+struct BrowserState {
+    search: String,
+    /// Schedule label and full name of the selected system.
+    selected: Option<(String, String)>,
+}
+
+/// Render the Schedules tab.
+pub fn render(ui: &mut egui::Ui, world: &mut World) {
+    let Some(graphs) = world.get_resource::<ScheduleGraphs>() else {
+        ui.label("Schedule capture is not available");
+        return;
+    };
+    if graphs.is_empty() {
+        ui.label("No schedules have been built yet.");
+        return;
+    }
+
+    let mut disabled = world
+        .get_resource::<DisabledSystems>()
+        .cloned()
+        .unwrap_or_default();
+    let disabled_before = disabled.clone();
+
+    let state_id = ui.id().with("schedule_browser");
+    let mut state: BrowserState = ui.data(|d| d.get_temp(state_id)).unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(&mut state.search).hint_text("Search systems and sets"));
+        if !state.search.is_empty() && ui.small_button("✖").clicked() {
+            state.search.clear();
+        }
+    });
+    ui.separator();
+
+    let search = state.search.to_lowercase();
+    egui::ScrollArea::vertical()
+        .id_salt("schedule_tree")
+        .max_height(ui.available_height() * 0.6)
+        .auto_shrink([false, true])
+        .show(ui, |ui| {
+            for schedule in graphs.iter() {
+                if search.is_empty() {
+                    egui::CollapsingHeader::new(format!(
+                        "{}  ({} systems)",
+                        schedule.label,
+                        schedule.systems.len()
+                    ))
+                    .id_salt(("schedule", &schedule.label))
+                    .show(ui, |ui| {
+                        render_children(
+                            ui,
+                            schedule,
+                            None,
+                            &mut state.selected,
+                            &mut disabled,
+                        );
+                    });
+                } else {
+                    render_matches(ui, schedule, &search, &mut state.selected, &mut disabled);
+                }
+            }
+        });
+
+    ui.separator();
+    let selected = state.selected.as_ref().and_then(|(label, name)| {
+        let schedule = graphs.get(label)?;
+        Some((schedule, schedule.system(name)?))
+    });
+    match selected {
+        Some((schedule, system)) => {
+            egui::ScrollArea::vertical()
+                .id_salt("schedule_details")
+                .show(ui, |ui| {
+                    if let Some(name) = render_details(ui, schedule, system, &mut disabled) {
+                        state.selected = Some((schedule.label.clone(), name));
+                    }
+                });
+        }
+        None => {
+            ui.weak("Select a system to see its ordering and data access.");
+        }
+    }
+
+    ui.data_mut(|d| d.insert_temp(state_id, state));
+    if disabled != disabled_before {
+        world.insert_resource(disabled);
+    }
+}
+
+/// Sets nested directly in `parent` and systems directly in it, or the
+/// top-level ones for `None`.
+fn render_children(
+    ui: &mut egui::Ui,
+    schedule: &ScheduleInfo,
+    parent: Option<&str>,
+    selected: &mut Option<(String, String)>,
+    disabled: &mut DisabledSystems,
+) {
+    let is_child = |parents: &[String]| match parent {
+        Some(parent) => parents.iter().any(|p| p == parent),
+        None => parents.is_empty(),
+    };
+
+    for set in schedule.sets.iter().filter(|set| is_child(&set.parents)) {
+        let id = ui.make_persistent_id(("schedule_set", &schedule.label, &set.name));
+        let (_, header, _) =
+            egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+                .show_header(ui, |ui| {
+                    toggle_checkbox(ui, &set.name, disabled);
+                    let mut text = egui::RichText::new(&set.name).strong();
+                    if disabled_by_set(schedule, &set.parents, disabled) {
+                        text = text.weak();
+                    }
+                    ui.label(text)
+                })
+                .body(|ui| {
+                    render_children(ui, schedule, Some(&set.name), selected, disabled);
+                });
+        let mut hover = Vec::new();
+        push_list(&mut hover, "Run conditions", &set.conditions);
+        push_list(&mut hover, "Runs after", &set.after);
+        push_list(&mut hover, "Runs before", &set.before);
+        if !hover.is_empty() {
+            header.inner.on_hover_text(hover.join("\n"));
+        }
+    }
+
+    for system in schedule
+        .systems
+        .iter()
+        .filter(|system| is_child(&system.sets))
+    {
+        ui.horizontal(|ui| system_label(ui, schedule, system, selected, disabled));
+    }
+}
+
+/// Flat list of the schedule's systems whose name or sets contain `search`.
+fn render_matches(
+    ui: &mut egui::Ui,
+    schedule: &ScheduleInfo,
+    search: &str,
+    selected: &mut Option<(String, String)>,
+    disabled: &mut DisabledSystems,
+) {
+    let matches: Vec<&SystemInfo> = schedule
+        .systems
+        .iter()
+        .filter(|system| {
+            system.name.to_lowercase().contains(search)
+                || system
+                    .sets
+                    .iter()
+                    .any(|set| set.to_lowercase().contains(search))
+        })
+        .collect();
+    if matches.is_empty() {
+        return;
+    }
+
+    ui.strong(&schedule.label);
+    ui.indent(("schedule_matches", &schedule.label), |ui| {
+        for system in matches {
+            ui.horizontal(|ui| {
+                system_label(ui, schedule, system, selected, disabled);
+                if !system.sets.is_empty() {
+                    ui.weak(system.sets.join(", "));
+                }
+            });
+        }
+    });
+}
+
+/// Checkbox and selectable name of a system.
+fn system_label(
+    ui: &mut egui::Ui,
+    schedule: &ScheduleInfo,
+    system: &SystemInfo,
+    selected: &mut Option<(String, String)>,
+    disabled: &mut DisabledSystems,
+) {
+    toggle_checkbox(ui, &system.name, disabled);
+    let is_selected = selected
+        .as_ref()
+        .is_some_and(|(label, name)| *label == schedule.label && *name == system.name);
+    let mut text = ShortName(&system.name).to_string();
+    if !system.conditions.is_empty() {
+        text.push_str("  (run_if)");
+    }
+    let mut text = egui::RichText::new(text);
+    if disabled_by_set(schedule, &system.sets, disabled) {
+        text = text.weak();
+    }
+    if ui
+        .selectable_label(is_selected, text)
+        .on_hover_text(&system.name)
+        .clicked()
+    {
+        *selected = Some((schedule.label.clone(), system.name.clone()));
+    }
+}
+
+/// Details of the selected system. Returns the name of another system in the
+/// same schedule if one of its ordering constraints was clicked.
+fn render_details(
+    ui: &mut egui::Ui,
+    schedule: &ScheduleInfo,
+    system: &SystemInfo,
+    disabled: &mut DisabledSystems,
+) -> Option<String> {
+    let mut clicked = None;
+
+    ui.horizontal(|ui| {
+        toggle_checkbox(ui, &system.name, disabled);
+        ui.heading(ShortName(&system.name).to_string());
+    });
+    ui.weak(&system.name);
+    if !disabled.is_disabled(&system.name) && disabled_by_set(schedule, &system.sets, disabled) {
+        ui.colored_label(ui.visuals().warn_fg_color, "Off because one of its sets is off");
+    }
+
+    egui::Grid::new("schedule_system_details")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Schedule");
+            ui.label(&schedule.label);
+            ui.end_row();
+
+            ui.strong("Sets");
+            names_cell(ui, &system.sets);
+            ui.end_row();
+
+            if system.is_exclusive {
+                ui.strong("Exclusive");
+                ui.label("Runs alone with `&mut World`");
+                ui.end_row();
+            }
+
+            ui.strong("Run conditions");
+            names_cell(ui, &system.conditions);
+            ui.end_row();
+
+            for (label, others) in [
+                ("Runs after", &system.after),
+                ("Runs before", &system.before),
+            ] {
+                ui.strong(label);
+                ui.vertical(|ui| {
+                    if others.is_empty() {
+                        ui.weak("-");
+                    }
+                    for other in others {
+                        let text = ShortName(other).to_string();
+                        if schedule.system(other).is_some() {
+                            if ui.link(text).on_hover_text(other).clicked() {
+                                clicked = Some(other.clone());
+                            }
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(4.0);
+    ui.strong("Data access");
+    render_access(ui, &system.access);
+
+    clicked
+}
+
+fn render_access(ui: &mut egui::Ui, access: &SystemAccess) {
+    egui::Grid::new("schedule_system_access")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            let rows = [
+                (
+                    "Reads components",
+                    access.reads_all_components && !access.writes_all_components,
+                    &access.component_reads,
+                ),
+                (
+                    "Writes components",
+                    access.writes_all_components,
+                    &access.component_writes,
+                ),
+                (
+                    "Reads resources",
+                    access.reads_all_resources && !access.writes_all_resources,
+                    &access.resource_reads,
+                ),
+                (
+                    "Writes resources",
+                    access.writes_all_resources,
+                    &access.resource_writes,
+                ),
+            ];
+            for (label, all, names) in rows {
+                ui.strong(label);
+                if all {
+                    ui.label("All");
+                } else {
+                    names_cell(ui, names);
+                }
+                ui.end_row();
+            }
+        });
+}
+
+fn names_cell(ui: &mut egui::Ui, names: &[String]) {
+    if names.is_empty() {
+        ui.weak("-");
+    } else {
+        ui.vertical(|ui| {
+            for name in names {
+                ui.label(ShortName(name).to_string()).on_hover_text(name);
+            }
+        });
+    }
+}
+
+fn push_list(lines: &mut Vec<String>, label: &str, names: &[String]) {
+    if !names.is_empty() {
+        let names: Vec<String> = names.iter().map(|n| ShortName(n).to_string()).collect();
+        lines.push(format!("{label}: {}", names.join(", ")));
+    }
+}
+
+/// Checkbox that turns the system or set called `name` on and off.
+fn toggle_checkbox(ui: &mut egui::Ui, name: &str, disabled: &mut DisabledSystems) {
+    if is_inspector_system(name) {
+        ui.add_enabled(false, egui::Checkbox::without_text(&mut true))
+            .on_disabled_hover_text("The inspector's own systems stay on");
+        return;
+    }
+    let mut enabled = !disabled.is_disabled(name);
+    if ui
+        .add(egui::Checkbox::without_text(&mut enabled))
+        .on_hover_text(if enabled { "Turn off" } else { "Turn on" })
+        .changed()
+    {
+        disabled.toggle(name);
+    }
+}
+
+/// Whether any of `sets`, or a set they are nested in, is turned off.
+fn disabled_by_set(schedule: &ScheduleInfo, sets: &[String], disabled: &DisabledSystems) -> bool {
+    !disabled.is_empty()
+        && sets.iter().any(|name| {
+            disabled.is_disabled(name)
+                || schedule
+                    .set(name)
+                    .is_some_and(|set| disabled_by_set(schedule, &set.parents, disabled))
+        })
+}
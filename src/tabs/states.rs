@@ -0,0 +1,97 @@
+//! States tab: current value and recent transitions of every reflected state,
+//! with a dropdown to force a transition.
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use crate::state_history::{self, StateHistory, TrackedState};
+
+/// Render the States tab.
+pub fn render(ui: &mut egui::Ui, world: &mut World) {
+    let Some(history) = world.get_resource::<StateHistory>() else {
+        ui.label("State tracking is not available");
+        return;
+    };
+    if history.is_empty() {
+        ui.label("No states registered for reflection.");
+        ui.weak("Register them with `app.register_type_mutable_state::<GameState>()`.");
+        return;
+    }
+
+    let mut transition = None;
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for state in history.iter() {
+            ui.horizontal(|ui| {
+                ui.strong(&state.name);
+                match &state.current {
+                    Some(value) => ui.monospace(value),
+                    None => ui.weak("not present"),
+                };
+                if let Some(variant) = switcher(ui, state) {
+                    transition = Some((state.type_id, variant));
+                }
+            });
+
+            egui::CollapsingHeader::new(format!("Transitions ({})", state.transitions().len()))
+                .id_salt(("state_transitions", &state.name))
+                .show(ui, |ui| transition_table(ui, state));
+            ui.separator();
+        }
+    });
+
+    if let Some((type_id, variant)) = transition
+        && !state_history::set_next_state(world, type_id, &variant)
+    {
+        warn!("Cannot switch state to {variant}: it has no reflected `NextState`");
+    }
+}
+
+/// Dropdown of the state's variants. Returns the variant picked this frame.
+fn switcher(ui: &mut egui::Ui, state: &TrackedState) -> Option<String> {
+    if !state.can_set || state.variants.is_empty() {
+        return None;
+    }
+    let mut picked = None;
+    egui::ComboBox::from_id_salt(("state_switcher", &state.name))
+        .selected_text("Go to…")
+        .show_ui(ui, |ui| {
+            for variant in &state.variants {
+                let is_current = state.current.as_deref() == Some(variant.as_str());
+                if ui.selectable_label(is_current, variant).clicked() {
+                    picked = Some(variant.clone());
+                }
+            }
+        });
+    picked
+}
+
+/// Frame and values of each recorded transition, newest first.
+fn transition_table(ui: &mut egui::Ui, state: &TrackedState) {
+    if state.transitions().next().is_none() {
+        ui.weak("No transitions yet");
+        return;
+    }
+    egui::Grid::new(("state_transition_grid", &state.name))
+        .num_columns(3)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("Frame");
+            ui.strong("From");
+            ui.strong("To");
+            ui.end_row();
+
+            for record in state.transitions().rev() {
+                ui.monospace(record.frame.to_string());
+                value_cell(ui, record.from.as_deref());
+                value_cell(ui, record.to.as_deref());
+                ui.end_row();
+            }
+        });
+}
+
+fn value_cell(ui: &mut egui::Ui, value: Option<&str>) {
+    match value {
+        Some(value) => ui.monospace(value),
+        None => ui.weak("-"),
+    };
+}
@@ -0,0 +1,176 @@
+//! Systems tab: per-system run times grouped by schedule and system set.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+use super::{Sort, SortColumn};
+use crate::system_timing::{SystemTiming, SystemTimings};
+
+/// Group for systems that are not in any of the main world's schedules.
+const OTHER_SCHEDULE: &str = "Other";
+/// Set column text for systems that are in no named set.
+const NO_SET: &str = "-";
+
+/// Column the system table is sorted by.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum Column {
+    Name,
+    Set,
+    #[default]
+    Mean,
+    Max,
+}
+
+impl SortColumn for Column {
+    // Names read best A to Z, times slowest first
+    fn ascending_first(self) -> bool {
+        matches!(self, Column::Name | Column::Set)
+    }
+}
+
+impl Sort<Column> {
+    fn apply(self, systems: &mut [&SystemTiming]) {
+        match self.column {
+            Column::Name => systems.sort_by(|a, b| a.name.cmp(&b.name)),
+            Column::Set => systems.sort_by(|a, b| a.sets.cmp(&b.sets)),
+            Column::Mean => systems.sort_by(|a, b| a.mean_ms().total_cmp(&b.mean_ms())),
+            Column::Max => systems.sort_by(|a, b| a.max_ms().total_cmp(&b.max_ms())),
+        }
+        if !self.ascending {
+            systems.reverse();
+        }
+    }
+}
+
+/// Render the Systems tab.
+pub fn render(ui: &mut egui::Ui, world: &World) {
+    let Some(timings) = world.get_resource::<SystemTimings>() else {
+        ui.label("System timing is not available");
+        return;
+    };
+    if timings.is_empty() {
+        ui.label("No system timings recorded yet.");
+        ui.weak(
+            "Build with the `trace` feature and set `LogPlugin::custom_layer` to \
+             `msg_inspector::system_timing_layer`.",
+        );
+        return;
+    }
+
+    let filter_id = ui.id().with("filter");
+    let mut filter: String = ui.data(|d| d.get_temp(filter_id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        ui.heading("Systems");
+        ui.add(egui::TextEdit::singleline(&mut filter).hint_text("Filter"));
+        let total: f64 = timings.iter().map(SystemTiming::mean_ms).sum();
+        ui.weak(format!(
+            "{} systems, {} per frame over {} frames",
+            timings.len(),
+            format_ms(total),
+            timings.history_length()
+        ));
+    });
+    let filter_lower = filter.to_lowercase();
+    ui.data_mut(|d| d.insert_temp(filter_id, filter));
+
+    // Group by schedule, slowest schedule first
+    let mut schedules: BTreeMap<&str, Vec<&SystemTiming>> = BTreeMap::new();
+    for system in timings.iter() {
+        if filter_lower.is_empty() || system.name.to_lowercase().contains(&filter_lower) {
+            let schedule = system.schedule.as_deref().unwrap_or(OTHER_SCHEDULE);
+            schedules.entry(schedule).or_default().push(system);
+        }
+    }
+    let mut schedules: Vec<(&str, Vec<&SystemTiming>, f64)> = schedules
+        .into_iter()
+        .map(|(schedule, systems)| {
+            let total = systems.iter().map(|system| system.mean_ms()).sum();
+            (schedule, systems, total)
+        })
+        .collect();
+    schedules.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let sort_id = ui.id().with("sort");
+    let mut sort: Sort<Column> = ui.data(|d| d.get_temp(sort_id)).unwrap_or_default();
+
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        for (schedule, mut systems, total) in schedules {
+            sort.apply(&mut systems);
+            egui::CollapsingHeader::new(format!(
+                "{schedule}  ({} systems, {})",
+                systems.len(),
+                format_ms(total)
+            ))
+            .id_salt(("system_timing_schedule", schedule))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new(("system_timing_grid", schedule))
+                    .num_columns(5)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        sort.header(ui, "System", Column::Name);
+                        sort.header(ui, "Set", Column::Set);
+                        sort.header(ui, "Mean", Column::Mean);
+                        sort.header(ui, "Max", Column::Max);
+                        ui.strong("History");
+                        ui.end_row();
+
+                        for system in systems {
+                            ui.label(ShortName(&system.name).to_string())
+                                .on_hover_text(&system.name);
+                            if system.sets.is_empty() {
+                                ui.weak(NO_SET);
+                            } else {
+                                ui.label(system.sets.join(", "));
+                            }
+                            ui.monospace(format_ms(system.mean_ms()));
+                            ui.monospace(format_ms(system.max_ms()));
+                            sparkline(ui, system);
+                            ui.end_row();
+                        }
+                    });
+            });
+        }
+    });
+
+    ui.data_mut(|d| d.insert_temp(sort_id, sort));
+}
+
+/// Draw the system's history scaled from zero to its slowest frame.
+fn sparkline(ui: &mut egui::Ui, system: &SystemTiming) {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(120.0, 16.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 1.0, ui.visuals().extreme_bg_color);
+
+    let max = system.max_ms();
+    let count = system.history().len();
+    if count < 2 || max <= 0.0 {
+        return;
+    }
+    let step = rect.width() / (count - 1) as f32;
+    let points: Vec<egui::Pos2> = system
+        .history()
+        .enumerate()
+        .map(|(i, ms)| {
+            egui::pos2(
+                rect.left() + i as f32 * step,
+                rect.bottom() - (ms / max) as f32 * rect.height(),
+            )
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.0, ui.visuals().text_color()),
+    ));
+    response.on_hover_text(format!("Last frame: {}", format_ms(system.last_ms())));
+}
+
+fn format_ms(ms: f64) -> String {
+    if ms < 1.0 {
+        format!("{:.1} µs", ms * 1000.0)
+    } else {
+        format!("{ms:.2} ms")
+    }
+}
@@ -0,0 +1,7 @@
+//! Internals that the integration tests drive directly. Not part of the public
+//! API and not covered by semver.
+
+pub use crate::{
+    tabs::{diagnostics::EntityCounts, hierarchy::search_entities},
+    undo::ValueSnapshot,
+};
@@ -0,0 +1,298 @@
+//! Pausing, single-stepping and slowing down the game clock.
+//!
+//! All of it works on [`Time<Virtual>`], so `Update` systems that use
+//! [`Time`] and everything in `FixedUpdate` stop while paused. The inspector's
+//! own UI runs on real time and stays usable. Stepping a number of frames
+//! unpauses the clock for exactly that many updates; stepping fixed ticks
+//! keeps it running until that many `FixedUpdate` runs have happened.
+
+use bevy::prelude::*;
+use bevy_egui::{EguiContext, PrimaryEguiContext, egui};
+
+use crate::config::InspectorEnabled;
+
+/// Slowest and fastest relative speed offered by the toolbar.
+const SPEED_RANGE: std::ops::RangeInclusive<f32> = 0.1..=10.0;
+/// Speeds the slower and faster hotkeys move between.
+const SPEED_STEPS: [f32; 7] = [0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0];
+
+/// What one press of the step button or key advances.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Reflect)]
+pub enum StepMode {
+    /// Whole frames of virtual time.
+    #[default]
+    Frames,
+    /// Runs of the `FixedUpdate` schedule.
+    FixedTicks,
+}
+
+/// Hotkeys and stepping state of the time controls in the menu bar.
+///
+/// The hotkeys only work while the inspector is shown and no egui text field
+/// has keyboard focus. Set a key to `None` to turn it off.
+///
+/// # Example
+///
+/// ```ignore
+/// app.insert_resource(TimeControl {
+///     pause_key: Some(KeyCode::KeyP),
+///     step_key: None,
+///     step_mode: StepMode::FixedTicks,
+///     ..default()
+/// });
+/// ```
+#[derive(Resource, Clone, Debug, Reflect)]
+#[reflect(Resource)]
+pub struct TimeControl {
+    /// Key that pauses and resumes the game clock.
+    pub pause_key: Option<KeyCode>,
+    /// Key that steps while paused.
+    pub step_key: Option<KeyCode>,
+    /// Key that moves to the next slower speed.
+    pub slower_key: Option<KeyCode>,
+    /// Key that moves to the next faster speed.
+    pub faster_key: Option<KeyCode>,
+    /// How many frames or ticks one step advances.
+    pub step_count: u32,
+    /// Whether a step advances frames or fixed ticks.
+    pub step_mode: StepMode,
+    frames_left: u32,
+    ticks_left: u32,
+    stepping: bool,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self {
+            pause_key: Some(KeyCode::F9),
+            step_key: Some(KeyCode::F10),
+            slower_key: Some(KeyCode::F7),
+            faster_key: Some(KeyCode::F8),
+            step_count: 1,
+            step_mode: StepMode::Frames,
+            frames_left: 0,
+            ticks_left: 0,
+            stepping: false,
+        }
+    }
+}
+
+impl TimeControl {
+    /// Let the game clock run for `frames` updates, then pause it.
+    pub fn step_frames(&mut self, frames: u32) {
+        self.frames_left = frames;
+        self.ticks_left = 0;
+    }
+
+    /// Let the game clock run until `FixedUpdate` has run `ticks` times, then pause it.
+    pub fn step_fixed_ticks(&mut self, ticks: u32) {
+        self.ticks_left = ticks;
+        self.frames_left = 0;
+    }
+
+    /// Step by [`step_count`](Self::step_count) in the current [`StepMode`].
+    pub fn step(&mut self) {
+        match self.step_mode {
+            StepMode::Frames => self.step_frames(self.step_count),
+            StepMode::FixedTicks => self.step_fixed_ticks(self.step_count),
+        }
+    }
+
+    /// Whether a step has been asked for and not finished yet.
+    pub fn is_stepping(&self) -> bool {
+        self.frames_left > 0 || self.ticks_left > 0 || self.stepping
+    }
+
+    /// Drop the current step, leaving the clock as it is.
+    pub fn cancel_step(&mut self) {
+        self.frames_left = 0;
+        self.ticks_left = 0;
+        self.stepping = false;
+    }
+}
+
+/// System that runs the clock for a requested step and pauses it afterwards.
+///
+/// Runs in `First` before [`TimeSystems`](bevy::time::TimeSystems), so a step
+/// asked for in one frame starts advancing time in the next.
+pub fn step_time(mut control: ResMut<TimeControl>, mut time: ResMut<Time<Virtual>>) {
+    if control.frames_left > 0 {
+        control.frames_left -= 1;
+        control.stepping = true;
+        time.unpause();
+    } else if control.ticks_left > 0 {
+        control.stepping = true;
+        time.unpause();
+    } else if control.stepping {
+        control.stepping = false;
+        time.pause();
+    }
+}
+
+/// System that counts `FixedUpdate` runs while stepping fixed ticks.
+///
+/// Runs in `FixedLast`. After the last tick of a step the rest of the
+/// accumulated time is dropped so no further tick runs this frame.
+pub fn count_fixed_steps(mut control: ResMut<TimeControl>, mut fixed: ResMut<Time<Fixed>>) {
+    if control.ticks_left == 0 {
+        return;
+    }
+    control.ticks_left -= 1;
+    if control.ticks_left == 0 {
+        let overstep = fixed.overstep();
+        fixed.discard_overstep(overstep);
+    }
+}
+
+/// System that handles the pause, step and speed hotkeys of [`TimeControl`].
+///
+/// The keys are left to the game while the inspector is hidden or egui wants
+/// keyboard input, e.g. while typing into a text field.
+pub fn time_control_hotkeys(
+    keys: Res<ButtonInput<KeyCode>>,
+    enabled: Res<InspectorEnabled>,
+    egui_context: Option<Single<&mut EguiContext, With<PrimaryEguiContext>>>,
+    mut control: ResMut<TimeControl>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    if !enabled.0
+        || egui_context.is_some_and(|mut context| context.get_mut().wants_keyboard_input())
+    {
+        return;
+    }
+    let pressed = |key: Option<KeyCode>| key.is_some_and(|key| keys.just_pressed(key));
+
+    if pressed(control.pause_key) {
+        toggle_pause(&mut control, &mut time);
+    }
+    if pressed(control.step_key) && time.is_paused() {
+        control.step();
+    }
+    if pressed(control.slower_key) {
+        let speed = SPEED_STEPS
+            .iter()
+            .rev()
+            .find(|speed| **speed < time.relative_speed())
+            .unwrap_or(SPEED_RANGE.start());
+        time.set_relative_speed(*speed);
+    }
+    if pressed(control.faster_key) {
+        let speed = SPEED_STEPS
+            .iter()
+            .find(|speed| **speed > time.relative_speed())
+            .unwrap_or(SPEED_RANGE.end());
+        time.set_relative_speed(*speed);
+    }
+}
+
+fn toggle_pause(control: &mut TimeControl, time: &mut Time<Virtual>) {
+    control.cancel_step();
+    if time.is_paused() {
+        time.unpause();
+    } else {
+        time.pause();
+    }
+}
+
+/// Pause, step and speed controls shown in the menu bar.
+pub(crate) fn toolbar_ui(ui: &mut egui::Ui, world: &mut World) {
+    if !world.contains_resource::<TimeControl>() {
+        return;
+    }
+    world.resource_scope(|world, mut control: Mut<TimeControl>| {
+        if let Some(mut time) = world.get_resource_mut::<Time<Virtual>>() {
+            controls(ui, &mut control, &mut time);
+        }
+    });
+}
+
+fn controls(ui: &mut egui::Ui, control: &mut Mut<TimeControl>, time: &mut Mut<Time<Virtual>>) {
+    let paused = time.is_paused() && !control.is_stepping();
+    let (icon, action) = if paused {
+        ("▶", "Resume")
+    } else {
+        ("⏸", "Pause")
+    };
+    if ui
+        .button(icon)
+        .on_hover_text(format!("{action}{}", key_hint(control.pause_key)))
+        .clicked()
+    {
+        toggle_pause(control, time);
+    }
+
+    let unit = match control.step_mode {
+        StepMode::Frames => "frames",
+        StepMode::FixedTicks => "fixed ticks",
+    };
+    if ui
+        .add_enabled(paused, egui::Button::new("⏭"))
+        .on_hover_text(format!(
+            "Step {} {unit}{}",
+            control.step_count,
+            key_hint(control.step_key)
+        ))
+        .clicked()
+    {
+        control.step();
+    }
+    let mut step_count = control.step_count;
+    if ui
+        .add(egui::DragValue::new(&mut step_count).range(1..=1000))
+        .changed()
+    {
+        control.step_count = step_count;
+    }
+    let mut step_mode = control.step_mode;
+    egui::ComboBox::from_id_salt("time_step_mode")
+        .selected_text(unit)
+        .width(80.0)
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut step_mode, StepMode::Frames, "frames");
+            ui.selectable_value(&mut step_mode, StepMode::FixedTicks, "fixed ticks");
+        });
+    if step_mode != control.step_mode {
+        control.step_mode = step_mode;
+    }
+
+    ui.separator();
+    let mut speed = time.relative_speed();
+    ui.scope(|ui| {
+        ui.spacing_mut().slider_width = 90.0;
+        if ui
+            .add(
+                egui::Slider::new(&mut speed, SPEED_RANGE)
+                    .logarithmic(true)
+                    .max_decimals(2)
+                    .suffix("x"),
+            )
+            .on_hover_text(speed_hint(control.slower_key, control.faster_key))
+            .changed()
+        {
+            time.set_relative_speed(speed);
+        }
+    });
+    if speed != 1.0 && ui.small_button("1x").clicked() {
+        time.set_relative_speed(1.0);
+    }
+    if paused {
+        ui.colored_label(ui.visuals().warn_fg_color, "Paused");
+    }
+}
+
+/// The key in parentheses for a tooltip, if there is one.
+fn key_hint(key: Option<KeyCode>) -> String {
+    key.map(|key| format!(" ({key:?})")).unwrap_or_default()
+}
+
+fn speed_hint(slower: Option<KeyCode>, faster: Option<KeyCode>) -> String {
+    let keys: Vec<String> = [(slower, "slower"), (faster, "faster")]
+        .into_iter()
+        .filter_map(|(key, what)| key.map(|key| format!("{key:?} {what}")))
+        .collect();
+    if keys.is_empty() {
+        "Game speed".to_string()
+    } else {
+        format!("Game speed ({})", keys.join(", "))
+    }
+}
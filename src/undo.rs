@@ -0,0 +1,579 @@
+//! Undo and redo of edits made through the inspector.
+//!
+//! While the user interacts with it, the Inspector tab copies the reflected
+//! values it shows before drawing them and compares the ones its widgets
+//! marked changed afterwards; every difference becomes an [`UndoEntry`] in
+//! [`UndoHistory`] holding the values before and after the edit. Entities
+//! spawned and despawned from the inspector are recorded as reflected
+//! snapshots, so only their reflected components come back on undo.
+
+use std::any::TypeId;
+
+use bevy::{
+    asset::{ReflectAsset, UntypedAssetId},
+    ecs::component::Tick,
+    prelude::*,
+    reflect::{PartialReflect, TypeRegistry},
+};
+
+use crate::reflection::clone_reflected;
+
+/// The value an edit changed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditTarget {
+    /// A component of an entity.
+    Component {
+        /// The entity holding the component.
+        entity: Entity,
+        /// Type of the component.
+        component: TypeId,
+    },
+    /// A resource, by type.
+    Resource(TypeId),
+    /// An asset, by asset type and id.
+    Asset(TypeId, UntypedAssetId),
+}
+
+/// One value changed by an edit.
+pub struct ValueChange {
+    /// What was changed.
+    pub target: EditTarget,
+    /// Short type name of the changed value, e.g. `Transform`.
+    pub name: String,
+    /// Copy of the value before the edit.
+    pub before: Box<dyn PartialReflect>,
+    /// Copy of the value after the edit.
+    pub after: Box<dyn PartialReflect>,
+}
+
+/// Reflected copy of an entity and its descendants.
+pub struct EntitySnapshot {
+    /// The entity the snapshot was taken of, or respawned as.
+    pub entity: Entity,
+    /// Parent the entity is attached to again when it is respawned.
+    pub parent: Option<Entity>,
+    /// Reflected components, by type. `ChildOf` and `Children` are rebuilt
+    /// from [`parent`](Self::parent) and [`children`](Self::children) instead.
+    pub components: Vec<(TypeId, Box<dyn PartialReflect>)>,
+    /// Snapshots of the entity's children.
+    pub children: Vec<EntitySnapshot>,
+}
+
+/// An edit that can be undone.
+pub enum InspectorEdit {
+    /// Values changed in the Inspector tab during one gesture.
+    Values(Vec<ValueChange>),
+    /// An entity spawned from the inspector.
+    Spawn(EntitySnapshot),
+    /// An entity despawned from the inspector, with its descendants.
+    Despawn(EntitySnapshot),
+}
+
+/// An edit and its description in the undo history.
+pub struct UndoEntry {
+    /// Description shown in the history list, e.g. `Edit Transform on Player`.
+    pub label: String,
+    /// The edit itself.
+    pub edit: InspectorEdit,
+    /// Drag or focused widget the edit was made with, so later frames of the
+    /// same drag extend the entry instead of adding new ones.
+    gesture: Option<u64>,
+}
+
+/// Resource holding the edits that can be undone and redone.
+#[derive(Resource)]
+pub struct UndoHistory {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    max_entries: usize,
+}
+
+impl Default for UndoHistory {
+    fn default() -> Self {
+        Self::new(100)
+    }
+}
+
+impl UndoHistory {
+    /// Keep the last `max_entries` edits.
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            max_entries: max_entries.max(1),
+        }
+    }
+
+    /// Edits that can be undone, oldest first.
+    pub fn undo_entries(&self) -> impl DoubleEndedIterator<Item = &UndoEntry> + ExactSizeIterator {
+        self.undo.iter()
+    }
+
+    /// Edits that can be redone, next one first.
+    pub fn redo_entries(&self) -> impl DoubleEndedIterator<Item = &UndoEntry> + ExactSizeIterator {
+        self.redo.iter().rev()
+    }
+
+    /// Whether there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    /// Whether there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forget every edit.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    /// Record an edit that has already been made. Clears the redo list.
+    pub fn push(&mut self, label: impl Into<String>, edit: InspectorEdit) {
+        self.push_entry(UndoEntry {
+            label: label.into(),
+            edit,
+            gesture: None,
+        });
+    }
+
+    /// Record value changes made by `gesture`, merging them into the last
+    /// entry if it was made by the same gesture on the same values.
+    pub(crate) fn push_values(&mut self, changes: Vec<ValueChange>, gesture: Option<u64>) {
+        if let Some(last) = self.undo.last_mut()
+            && gesture.is_some()
+            && last.gesture == gesture
+            && self.redo.is_empty()
+            && let InspectorEdit::Values(previous) = &mut last.edit
+            && previous.len() == changes.len()
+            && previous
+                .iter()
+                .zip(&changes)
+                .all(|(previous, change)| previous.target == change.target)
+        {
+            for (previous, change) in previous.iter_mut().zip(changes) {
+                previous.after = change.after;
+            }
+            return;
+        }
+        self.push_entry(UndoEntry {
+            label: values_label(&changes),
+            edit: InspectorEdit::Values(changes),
+            gesture,
+        });
+    }
+
+    fn push_entry(&mut self, entry: UndoEntry) {
+        self.redo.clear();
+        self.undo.push(entry);
+        if self.undo.len() > self.max_entries {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Point entries that refer to `from` at `to`, after an entity was respawned.
+    fn remap(&mut self, from: Entity, to: Entity) {
+        for entry in self.undo.iter_mut().chain(self.redo.iter_mut()) {
+            match &mut entry.edit {
+                InspectorEdit::Values(changes) => {
+                    for change in changes {
+                        if let EditTarget::Component { entity, .. } = &mut change.target
+                            && *entity == from
+                        {
+                            *entity = to;
+                        }
+                    }
+                }
+                InspectorEdit::Spawn(snapshot) | InspectorEdit::Despawn(snapshot) => {
+                    snapshot.remap(from, to);
+                }
+            }
+        }
+    }
+}
+
+impl EntitySnapshot {
+    /// Copy the reflected components of `entity` and its descendants.
+    pub fn take(world: &World, entity: Entity, registry: &TypeRegistry) -> Self {
+        let skipped = [TypeId::of::<ChildOf>(), TypeId::of::<Children>()];
+        let components = reflected_components(world, entity, registry)
+            .into_iter()
+            .filter(|(type_id, _, _)| !skipped.contains(type_id))
+            .map(|(type_id, _, value)| (type_id, value))
+            .collect();
+        let children = world
+            .get::<Children>(entity)
+            .map(|children| {
+                children
+                    .iter()
+                    .map(|child| Self::take(world, child, registry))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            entity,
+            parent: world.get::<ChildOf>(entity).map(ChildOf::parent),
+            components,
+            children,
+        }
+    }
+
+    /// Spawn the snapshot as new entities, returning each old entity and its
+    /// replacement.
+    fn respawn(&mut self, world: &mut World, registry: &TypeRegistry) -> Vec<(Entity, Entity)> {
+        let mut entity = world.spawn_empty();
+        for (type_id, value) in &self.components {
+            if let Some(component) = registry.get_type_data::<ReflectComponent>(*type_id) {
+                component.insert(&mut entity, value.as_ref(), registry);
+            }
+        }
+        let id = entity.id();
+        if let Some(parent) = self.parent
+            && let Ok(mut parent) = world.get_entity_mut(parent)
+        {
+            parent.add_child(id);
+        }
+
+        let mut respawned = vec![(self.entity, id)];
+        self.entity = id;
+        for child in &mut self.children {
+            child.parent = Some(id);
+            respawned.extend(child.respawn(world, registry));
+        }
+        respawned
+    }
+
+    fn remap(&mut self, from: Entity, to: Entity) {
+        if self.entity == from {
+            self.entity = to;
+        }
+        if self.parent == Some(from) {
+            self.parent = Some(to);
+        }
+        for child in &mut self.children {
+            child.remap(from, to);
+        }
+    }
+}
+
+/// Undo the last edit. Returns false if there was nothing to undo.
+pub fn undo(world: &mut World) -> bool {
+    step(world, Direction::Undo)
+}
+
+/// Redo the last undone edit. Returns false if there was nothing to redo.
+pub fn redo(world: &mut World) -> bool {
+    step(world, Direction::Redo)
+}
+
+/// Undo or redo until `remaining` edits are left to undo.
+pub fn restore_to(world: &mut World, remaining: usize) {
+    loop {
+        let Some(history) = world.get_resource::<UndoHistory>() else {
+            return;
+        };
+        let done = history.undo.len();
+        let moved = if done > remaining {
+            undo(world)
+        } else if done < remaining {
+            redo(world)
+        } else {
+            return;
+        };
+        if !moved {
+            return;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Undo,
+    Redo,
+}
+
+fn step(world: &mut World, direction: Direction) -> bool {
+    let Some(mut history) = world.get_resource_mut::<UndoHistory>() else {
+        return false;
+    };
+    let entry = match direction {
+        Direction::Undo => history.undo.pop(),
+        Direction::Redo => history.redo.pop(),
+    };
+    let Some(mut entry) = entry else {
+        return false;
+    };
+
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let mut respawned = Vec::new();
+    match (&mut entry.edit, direction) {
+        (InspectorEdit::Values(changes), _) => {
+            for change in changes.iter() {
+                let value = match direction {
+                    Direction::Undo => change.before.as_ref(),
+                    Direction::Redo => change.after.as_ref(),
+                };
+                apply_value(world, change.target, value, &registry);
+            }
+        }
+        (InspectorEdit::Spawn(snapshot), Direction::Undo)
+        | (InspectorEdit::Despawn(snapshot), Direction::Redo) => {
+            // Keep edits made since, so redoing brings them back. If the game
+            // despawned the entity already, the recorded snapshot is all there is
+            if world.get_entity(snapshot.entity).is_ok() {
+                *snapshot = EntitySnapshot {
+                    parent: snapshot.parent,
+                    ..EntitySnapshot::take(world, snapshot.entity, &registry)
+                };
+                world.despawn(snapshot.entity);
+            }
+        }
+        (InspectorEdit::Spawn(snapshot), Direction::Redo)
+        | (InspectorEdit::Despawn(snapshot), Direction::Undo) => {
+            respawned = snapshot.respawn(world, &registry);
+        }
+    }
+
+    let mut history = world.resource_mut::<UndoHistory>();
+    for (from, to) in respawned {
+        history.remap(from, to);
+    }
+    match direction {
+        Direction::Undo => history.redo.push(entry),
+        Direction::Redo => history.undo.push(entry),
+    }
+    true
+}
+
+/// Set the value behind `target` to `value`, inserting a missing component.
+fn apply_value(
+    world: &mut World,
+    target: EditTarget,
+    value: &dyn PartialReflect,
+    registry: &TypeRegistry,
+) {
+    let applied = match target {
+        EditTarget::Component { entity, component } => {
+            let (Ok(mut entity), Some(reflect)) = (
+                world.get_entity_mut(entity),
+                registry.get_type_data::<ReflectComponent>(component),
+            ) else {
+                return;
+            };
+            match reflect.reflect_mut(&mut entity) {
+                Some(mut current) => current.try_apply(value).is_ok(),
+                None => {
+                    reflect.insert(&mut entity, value, registry);
+                    true
+                }
+            }
+        }
+        EditTarget::Resource(type_id) => registry
+            .get_type_data::<ReflectResource>(type_id)
+            .and_then(|reflect| reflect.reflect_mut(world).ok())
+            .is_some_and(|mut current| current.try_apply(value).is_ok()),
+        EditTarget::Asset(type_id, id) => registry
+            .get_type_data::<ReflectAsset>(type_id)
+            .and_then(|reflect| reflect.get_mut(world, id))
+            .is_some_and(|current| current.try_apply(value).is_ok()),
+    };
+    if !applied {
+        warn!("Could not restore {target:?}");
+    }
+}
+
+/// Reflected values the Inspector tab may change this frame.
+pub struct ValueSnapshot(Vec<(EditTarget, String, Box<dyn PartialReflect>)>);
+
+impl ValueSnapshot {
+    /// Components of `entities`, and of their descendants if `with_children`.
+    pub fn entities(
+        world: &World,
+        entities: &[Entity],
+        with_children: bool,
+        registry: &TypeRegistry,
+    ) -> Self {
+        let mut values = Vec::new();
+        let mut stack = entities.to_vec();
+        while let Some(entity) = stack.pop() {
+            for (component, name, value) in reflected_components(world, entity, registry) {
+                values.push((EditTarget::Component { entity, component }, name, value));
+            }
+            if with_children && let Some(children) = world.get::<Children>(entity) {
+                stack.extend(children.iter());
+            }
+        }
+        Self(values)
+    }
+
+    /// A single resource or asset.
+    pub fn value(world: &World, target: EditTarget, registry: &TypeRegistry) -> Self {
+        let value = match target {
+            EditTarget::Component { .. } => None,
+            EditTarget::Resource(type_id) => registry
+                .get_type_data::<ReflectResource>(type_id)
+                .and_then(|reflect| reflect.reflect(world).ok())
+                .map(|value| clone_reflected(value.as_partial_reflect())),
+            EditTarget::Asset(type_id, id) => registry
+                .get_type_data::<ReflectAsset>(type_id)
+                .and_then(|reflect| reflect.get(world, id))
+                .map(|value| clone_reflected(value.as_partial_reflect())),
+        };
+        let name = match target {
+            EditTarget::Resource(type_id) | EditTarget::Asset(type_id, _) => {
+                short_name(registry, type_id)
+            }
+            EditTarget::Component { .. } => String::new(),
+        };
+        Self(
+            value
+                .map(|value| (target, name, value))
+                .into_iter()
+                .collect(),
+        )
+    }
+
+    /// Record every value that changed since the snapshot in the world's [`UndoHistory`].
+    ///
+    /// Only components marked changed after `since` are compared, as the
+    /// inspector marks the components it writes to.
+    pub fn record_changes(
+        self,
+        world: &mut World,
+        since: Tick,
+        gesture: Option<u64>,
+        registry: &TypeRegistry,
+    ) {
+        let this_run = world.change_tick();
+        let changes: Vec<ValueChange> = self
+            .0
+            .into_iter()
+            .filter(|(target, _, _)| match *target {
+                EditTarget::Component { entity, component } => world
+                    .components()
+                    .get_id(component)
+                    .zip(world.get_entity(entity).ok())
+                    .and_then(|(id, entity)| entity.get_change_ticks_by_id(id))
+                    .is_some_and(|ticks| ticks.is_changed(since, this_run)),
+                EditTarget::Resource(_) | EditTarget::Asset(..) => true,
+            })
+            .filter_map(|(target, name, before)| {
+                let after = match target {
+                    EditTarget::Component { entity, component } => world
+                        .get_entity(entity)
+                        .ok()
+                        .zip(registry.get_type_data::<ReflectComponent>(component))
+                        .and_then(|(entity, reflect)| reflect.reflect(entity))
+                        .map(|value| value.as_partial_reflect()),
+                    EditTarget::Resource(type_id) => registry
+                        .get_type_data::<ReflectResource>(type_id)
+                        .and_then(|reflect| reflect.reflect(&*world).ok())
+                        .map(|value| value.as_partial_reflect()),
+                    EditTarget::Asset(type_id, id) => registry
+                        .get_type_data::<ReflectAsset>(type_id)
+                        .and_then(|reflect| reflect.get(world, id))
+                        .map(|value| value.as_partial_reflect()),
+                }?;
+                (!same_value(before.as_ref(), after)).then(|| ValueChange {
+                    target,
+                    name,
+                    after: clone_reflected(after),
+                    before,
+                })
+            })
+            .collect();
+        if changes.is_empty() {
+            return;
+        }
+        if let Some(mut history) = world.get_resource_mut::<UndoHistory>() {
+            history.push_values(changes, gesture);
+        }
+    }
+}
+
+/// Spawn an empty named entity and record it in the [`UndoHistory`].
+pub(crate) fn spawn_entity(world: &mut World) -> Entity {
+    let entity = world.spawn(Name::new("Entity")).id();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let snapshot = EntitySnapshot::take(world, entity, &registry.read());
+    if let Some(mut history) = world.get_resource_mut::<UndoHistory>() {
+        history.push(format!("Spawn {entity}"), InspectorEdit::Spawn(snapshot));
+    }
+    entity
+}
+
+/// Despawn `entity` and its descendants, recording them in the [`UndoHistory`].
+pub(crate) fn despawn_entity(world: &mut World, entity: Entity, registry: &TypeRegistry) {
+    if world.get_entity(entity).is_err() {
+        return;
+    }
+    let label = format!("Despawn {}", entity_label(world, entity));
+    let snapshot = EntitySnapshot::take(world, entity, registry);
+    world.despawn(entity);
+    if let Some(mut history) = world.get_resource_mut::<UndoHistory>() {
+        history.push(label, InspectorEdit::Despawn(snapshot));
+    }
+}
+
+/// Every reflected component of `entity` with its type, short name and a copy
+/// of its value.
+fn reflected_components(
+    world: &World,
+    entity: Entity,
+    registry: &TypeRegistry,
+) -> Vec<(TypeId, String, Box<dyn PartialReflect>)> {
+    let Ok(entity_ref) = world.get_entity(entity) else {
+        return Vec::new();
+    };
+    entity_ref
+        .archetype()
+        .components()
+        .iter()
+        .filter_map(|id| {
+            let type_id = world.components().get_info(*id)?.type_id()?;
+            let value = registry
+                .get_type_data::<ReflectComponent>(type_id)?
+                .reflect(entity_ref)?;
+            Some((
+                type_id,
+                short_name(registry, type_id),
+                clone_reflected(value.as_partial_reflect()),
+            ))
+        })
+        .collect()
+}
+
+/// Whether two reflected values are equal, comparing their `Debug` output for
+/// types that do not support reflected equality.
+fn same_value(a: &dyn PartialReflect, b: &dyn PartialReflect) -> bool {
+    a.reflect_partial_eq(b)
+        .unwrap_or_else(|| format!("{a:?}") == format!("{b:?}"))
+}
+
+fn short_name(registry: &TypeRegistry, type_id: TypeId) -> String {
+    registry.get_type_info(type_id).map_or_else(
+        || format!("{type_id:?}"),
+        |info| info.type_path_table().short_path().to_string(),
+    )
+}
+
+fn entity_label(world: &World, entity: Entity) -> String {
+    match world.get::<Name>(entity) {
+        Some(name) => format!("{name} ({entity})"),
+        None => entity.to_string(),
+    }
+}
+
+fn values_label(changes: &[ValueChange]) -> String {
+    match changes {
+        [change] => match change.target {
+            EditTarget::Component { entity, .. } => format!("Edit {} on {entity}", change.name),
+            EditTarget::Resource(_) => format!("Edit resource {}", change.name),
+            EditTarget::Asset(..) => format!("Edit asset {}", change.name),
+        },
+        changes => format!("Edit {} values", changes.len()),
+    }
+}
@@ -7,13 +7,10 @@ use bevy::{
 };
 use bevy_inspector_egui::bevy_egui::{EguiContextSettings, PrimaryEguiContext};
 
-use crate::state::{GameViewportRect, InspectorEnabled, UiState};
-
-/// Marker component for the main game camera.
-///
-/// Games should add this component to their primary camera for viewport management.
-#[derive(Component)]
-pub struct InspectorMainCamera;
+use crate::{
+    config::{InspectorEnabled, InspectorMainCamera},
+    state::{GameViewportRect, UiState},
+};
 
 /// System that adjusts the camera viewport to not overlap with egui panels.
 pub fn set_camera_viewport(
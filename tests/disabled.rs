@@ -0,0 +1,45 @@
+//! Checks that the registration API still compiles and runs with the
+//! `inspector` feature turned off.
+
+#![cfg(not(feature = "inspector"))]
+
+use bevy::prelude::*;
+use msg_inspector::{egui, prelude::*};
+
+struct StatsTab;
+
+impl InspectorTab for StatsTab {
+    fn id(&self) -> &'static str {
+        "stats"
+    }
+
+    fn title(&self) -> &str {
+        "Stats"
+    }
+
+    fn ui(&mut self, ui: &mut egui::Ui, world: &mut World) {
+        ui.label(format!("{} entities", world.entities().len()));
+    }
+}
+
+#[test]
+fn test_disabled_inspector_is_a_no_op() {
+    let mut app = App::new();
+    app.add_plugins(
+        InspectorPlugin::new()
+            .toggle_key(KeyCode::F12)
+            .without_tab(BuiltinTab::Assets),
+    )
+    .register_inspector_tab(StatsTab)
+    .register_inspector_analytics("analytics", "Analytics", |ui, world| {
+        ui.label(format!("{} entities", world.entities().len()));
+    })
+    .add_systems(Update, |mut commands: Commands| {
+        commands.add_inspector_tab(StatsTab);
+        commands.remove_inspector_tab("stats");
+    })
+    .add_systems(Update, (|| {}).run_if(not(egui_pointer_over_area)));
+    app.update();
+
+    assert!(!app.world().contains_resource::<InspectorEnabled>());
+}
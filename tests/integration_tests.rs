@@ -1,4 +1,5 @@
 //! Integration tests for msg_inspector with Bevy 0.17.
+#![cfg(feature = "inspector")]
 
 use bevy::prelude::*;
 use msg_inspector::prelude::*;
@@ -132,3 +133,1386 @@ fn _compile_test_custom_tab_registration() {
     let mut registry = InspectorTabRegistry::default();
     registry.register(TestTab);
 }
+
+#[test]
+fn test_default_layout_honors_dock_position() {
+    struct PositionedTab(DockPosition);
+
+    impl InspectorTab for PositionedTab {
+        fn id(&self) -> &'static str {
+            "positioned"
+        }
+
+        fn title(&self) -> &str {
+            "Positioned"
+        }
+
+        fn ui(&mut self, _ui: &mut msg_inspector::egui::Ui, _world: &mut World) {}
+
+        fn dock_position(&self) -> DockPosition {
+            self.0
+        }
+    }
+
+    let cases = [
+        (DockPosition::Left, BuiltinTab::Hierarchy),
+        (DockPosition::Right, BuiltinTab::Inspector),
+        (DockPosition::Bottom, BuiltinTab::Assets),
+        (DockPosition::Center, BuiltinTab::GameView),
+    ];
+
+    for (position, neighbour) in cases {
+        let mut ui_state = UiState::new();
+        ui_state.set_custom_tabs(vec![Box::new(PositionedTab(position))]);
+        ui_state.build_default_layout();
+
+        let (custom_node, _) = ui_state
+            .state
+            .find_main_surface_tab(&Tab::Custom("positioned".to_string()))
+            .expect("custom tab should be in the layout");
+        let (neighbour_node, _) = ui_state
+            .state
+            .find_main_surface_tab(&Tab::Builtin(neighbour))
+            .expect("builtin tab should be in the layout");
+        assert_eq!(custom_node, neighbour_node, "{position:?} tab misplaced");
+    }
+}
+
+#[test]
+fn test_layout_roundtrip_restores_custom_tabs_by_id() {
+    struct NamedTab(&'static str);
+
+    impl InspectorTab for NamedTab {
+        fn id(&self) -> &'static str {
+            self.0
+        }
+
+        fn title(&self) -> &str {
+            self.0
+        }
+
+        fn ui(&mut self, _ui: &mut msg_inspector::egui::Ui, _world: &mut World) {}
+    }
+
+    let mut ui_state = UiState::new();
+    ui_state.set_custom_tabs(vec![Box::new(NamedTab("kept")), Box::new(NamedTab("removed"))]);
+    ui_state.build_default_layout();
+
+    let path = std::env::temp_dir().join(format!(
+        "msg_inspector_layout_roundtrip_{}.ron",
+        std::process::id()
+    ));
+    msg_inspector::save_layout(&ui_state.saved_layout(), &path).expect("layout should save");
+    let saved = msg_inspector::load_layout(&path).expect("layout should load");
+    let _ = std::fs::remove_file(&path);
+
+    // Next run: "removed" is gone, "added" is new, and registration order changed
+    let mut ui_state = UiState::new();
+    ui_state.set_custom_tabs(vec![Box::new(NamedTab("added")), Box::new(NamedTab("kept"))]);
+    ui_state.restore_layout(saved);
+
+    let has_tab = |id: &str| ui_state.state.find_tab(&Tab::Custom(id.to_string())).is_some();
+    assert!(has_tab("kept"));
+    assert!(has_tab("added"));
+    assert!(!has_tab("removed"));
+}
+
+#[test]
+fn test_tab_registry_rejects_duplicate_ids() {
+    struct DuplicateTab;
+
+    impl InspectorTab for DuplicateTab {
+        fn id(&self) -> &'static str {
+            "duplicate"
+        }
+
+        fn title(&self) -> &str {
+            "Duplicate"
+        }
+
+        fn ui(&mut self, _ui: &mut msg_inspector::egui::Ui, _world: &mut World) {}
+    }
+
+    let mut registry = InspectorTabRegistry::default();
+    assert!(registry.try_register(DuplicateTab).is_ok());
+    assert_eq!(
+        registry.try_register(DuplicateTab),
+        Err(msg_inspector::DuplicateTabId("duplicate"))
+    );
+
+    // The logging variant keeps the first registration only
+    registry.register(DuplicateTab);
+    assert_eq!(registry.len(), 1);
+    assert!(registry.contains("duplicate"));
+}
+
+#[test]
+fn test_ui_state_add_and_remove_custom_tabs() {
+    struct LevelTab;
+
+    impl InspectorTab for LevelTab {
+        fn id(&self) -> &'static str {
+            "level"
+        }
+
+        fn title(&self) -> &str {
+            "Level"
+        }
+
+        fn ui(&mut self, _ui: &mut msg_inspector::egui::Ui, _world: &mut World) {}
+
+        fn dock_position(&self) -> DockPosition {
+            DockPosition::Right
+        }
+    }
+
+    let mut ui_state = UiState::new();
+    ui_state.build_default_layout();
+
+    assert!(ui_state.add_custom_tab(Box::new(LevelTab)).is_ok());
+    assert!(ui_state.add_custom_tab(Box::new(LevelTab)).is_err());
+    assert_eq!(ui_state.custom_tab_ids().collect::<Vec<_>>(), ["level"]);
+
+    let (tab_node, _) = ui_state
+        .state
+        .find_main_surface_tab(&Tab::custom("level"))
+        .expect("added tab should be docked");
+    let (inspector_node, _) = ui_state
+        .state
+        .find_main_surface_tab(&Tab::Builtin(BuiltinTab::Inspector))
+        .unwrap();
+    assert_eq!(tab_node, inspector_node);
+
+    assert!(ui_state.remove_custom_tab("level").is_some());
+    assert!(ui_state.state.find_tab(&Tab::custom("level")).is_none());
+    assert_eq!(ui_state.custom_tab_ids().count(), 0);
+}
+
+#[test]
+fn test_remove_inspector_tab_accepts_runtime_ids() {
+    struct LevelTab;
+
+    impl InspectorTab for LevelTab {
+        fn id(&self) -> &'static str {
+            "level_3"
+        }
+
+        fn title(&self) -> &str {
+            "Level 3"
+        }
+
+        fn ui(&mut self, _ui: &mut msg_inspector::egui::Ui, _world: &mut World) {}
+    }
+
+    let mut app = App::new();
+    app.init_resource::<InspectorTabRegistry>();
+    app.world_mut()
+        .resource_mut::<InspectorTabRegistry>()
+        .register(LevelTab);
+
+    let level = 3;
+    app.world_mut()
+        .commands()
+        .remove_inspector_tab(format!("level_{level}"));
+    app.world_mut().flush();
+
+    assert!(app.world().resource::<InspectorTabRegistry>().is_empty());
+}
+
+#[test]
+fn test_ui_state_close_and_reopen_builtin_tabs() {
+    let mut ui_state = UiState::new();
+    ui_state.build_default_layout();
+
+    for builtin in BuiltinTab::ALL {
+        let tab = Tab::Builtin(builtin);
+        assert!(ui_state.is_tab_open(&tab), "{builtin:?} should start open");
+
+        ui_state.close_tab(&tab);
+        assert!(!ui_state.is_tab_open(&tab), "{builtin:?} should be closed");
+
+        assert!(ui_state.open_tab(tab.clone()));
+        assert!(ui_state.is_tab_open(&tab), "{builtin:?} should be reopened");
+    }
+
+    // Unknown custom tabs cannot be opened
+    assert!(!ui_state.open_tab(Tab::custom("missing")));
+}
+
+#[test]
+fn test_layout_presets() {
+    let mut ui_state = UiState::new();
+    ui_state.build_default_layout();
+    ui_state.save_layout_preset("Default");
+
+    let perf = msg_inspector::LayoutBuilder::new([BuiltinTab::Diagnostics])
+        .right(0.3, [BuiltinTab::GameView])
+        .bottom(0.2, [Tab::custom("unregistered")])
+        .build();
+    ui_state.register_layout_preset("Performance", perf);
+    assert_eq!(
+        ui_state.layout_preset_names().collect::<Vec<_>>(),
+        ["Default", "Performance"]
+    );
+
+    assert!(ui_state.apply_layout_preset("Performance"));
+    assert!(ui_state.is_tab_open(&Tab::Builtin(BuiltinTab::Diagnostics)));
+    assert!(!ui_state.is_tab_open(&Tab::Builtin(BuiltinTab::Inspector)));
+    // Tabs that are not registered are dropped from the preset
+    assert!(!ui_state.is_tab_open(&Tab::custom("unregistered")));
+
+    assert!(ui_state.apply_layout_preset("Default"));
+    assert!(ui_state.is_tab_open(&Tab::Builtin(BuiltinTab::Inspector)));
+
+    assert!(!ui_state.apply_layout_preset("Missing"));
+    assert!(ui_state.remove_layout_preset("Performance"));
+    assert_eq!(ui_state.layout_preset_names().count(), 1);
+}
+
+#[test]
+fn test_disabled_builtin_tabs_are_left_out() {
+    let mut ui_state = UiState::new();
+    ui_state.set_builtin_tabs(
+        BuiltinTab::ALL
+            .into_iter()
+            .filter(|tab| *tab != BuiltinTab::Assets),
+    );
+    ui_state.build_default_layout();
+
+    assert!(!ui_state.is_tab_open(&Tab::Builtin(BuiltinTab::Assets)));
+    assert!(ui_state.is_tab_open(&Tab::Builtin(BuiltinTab::GameView)));
+    assert!(!ui_state.open_tab(Tab::Builtin(BuiltinTab::Assets)));
+}
+
+/// Verifies the plugin builder API compiles.
+#[allow(dead_code)]
+fn _compile_test_plugin_builder() -> InspectorPlugin {
+    InspectorPlugin::new()
+        .toggle_key(KeyCode::F12)
+        .start_hidden()
+        .without_tab(BuiltinTab::Assets)
+        .with_tab(BuiltinTab::Observers)
+        .without_picking()
+        .with_preview_lighting()
+}
+
+#[test]
+fn test_preview_lighting_restores_game_ambient_light() {
+    use bevy::light::AmbientLight;
+    use msg_inspector::{PreviewLight, sync_preview_lighting};
+
+    let mut app = App::new();
+    app.insert_resource(InspectorEnabled(true))
+        .insert_resource(PreviewLighting { enabled: true })
+        .insert_resource(AmbientLight {
+            brightness: 500.0,
+            ..default()
+        })
+        .add_systems(Update, sync_preview_lighting);
+
+    app.update();
+    let world = app.world_mut();
+    assert_ne!(world.resource::<AmbientLight>().brightness, 500.0);
+    let mut q_lights = world.query_filtered::<(), With<PreviewLight>>();
+    assert_eq!(q_lights.iter(world).count(), 1);
+
+    // Hiding the inspector undoes preview lighting
+    world.resource_mut::<InspectorEnabled>().0 = false;
+    app.update();
+    let world = app.world_mut();
+    assert_eq!(world.resource::<AmbientLight>().brightness, 500.0);
+    assert_eq!(q_lights.iter(world).count(), 0);
+
+    // An ambient light the game sets while preview lighting is on is kept
+    world.resource_mut::<InspectorEnabled>().0 = true;
+    app.update();
+    app.world_mut().resource_mut::<AmbientLight>().brightness = 42.0;
+    app.world_mut().resource_mut::<PreviewLighting>().enabled = false;
+    app.update();
+    assert_eq!(app.world().resource::<AmbientLight>().brightness, 42.0);
+}
+
+#[test]
+fn test_inspector_owned_entities_are_left_out_of_hierarchy_and_count() {
+    use msg_inspector::testing::{EntityCounts, search_entities};
+
+    let mut world = World::new();
+    let crate_entity = world.spawn(Name::new("Crate")).id();
+    let light = world
+        .spawn((msg_inspector::PreviewLight, Name::new("Crate Light")))
+        .id();
+    assert!(world.entity(light).contains::<InspectorInternal>());
+
+    // Internal entities are hidden from the hierarchy by default
+    assert!(!UiState::new().show_internal_entities);
+    let listed = |world: &mut World, query: &str, show_internal: bool| {
+        search_entities(world, query, show_internal)
+            .into_iter()
+            .map(|(entity, _)| entity)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(listed(&mut world, "", false), [crate_entity]);
+    assert_eq!(listed(&mut world, "crate", false), [crate_entity]);
+    assert_eq!(listed(&mut world, "crate", true), [crate_entity, light]);
+
+    assert_eq!(
+        EntityCounts::collect(&world),
+        EntityCounts {
+            game: 1,
+            internal: 1
+        }
+    );
+}
+
+#[test]
+fn test_diagnostic_stats_only_use_the_window() {
+    use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath};
+    use bevy::platform::time::Instant;
+    use msg_inspector::DiagnosticStats;
+    use std::time::Duration;
+
+    let start = Instant::now();
+    let mut diagnostic =
+        Diagnostic::new(DiagnosticPath::const_new("frame_time")).with_max_history_length(200);
+    // An old spike, then 100 frames of 1..=100 ms one second apart
+    diagnostic.add_measurement(DiagnosticMeasurement {
+        time: start,
+        value: 500.0,
+    });
+    for i in 1..=100 {
+        diagnostic.add_measurement(DiagnosticMeasurement {
+            time: start + Duration::from_secs(10 + i),
+            value: i as f64,
+        });
+    }
+
+    let stats = DiagnosticStats::over_window(&diagnostic, Duration::from_secs(99)).unwrap();
+    assert_eq!(stats.samples, 100);
+    assert_eq!((stats.min, stats.max), (1.0, 100.0));
+    assert_eq!(stats.average, 50.5);
+    assert_eq!(stats.p95, 95.0);
+
+    let all = DiagnosticStats::over_window(&diagnostic, Duration::from_secs(1000)).unwrap();
+    assert_eq!(all.max, 500.0);
+}
+
+#[test]
+fn test_diagnostics_are_grouped_by_path_prefix() {
+    use bevy::diagnostic::{Diagnostic, DiagnosticPath};
+    use msg_inspector::group_diagnostics;
+
+    let diagnostics = [
+        "physics/step_time",
+        "fps",
+        "render/gpu/draw_calls",
+        "frame_time",
+        "physics/bodies",
+    ]
+    .map(|path| Diagnostic::new(DiagnosticPath::new(path)));
+
+    let groups: Vec<(&str, Vec<&str>)> = group_diagnostics(&diagnostics)
+        .into_iter()
+        .map(|(group, members)| {
+            let paths = members.iter().map(|d| d.path().as_str()).collect();
+            (group, paths)
+        })
+        .collect();
+    assert_eq!(
+        groups,
+        [
+            ("General", vec!["fps", "frame_time"]),
+            ("physics", vec!["physics/bodies", "physics/step_time"]),
+            ("render/gpu", vec!["render/gpu/draw_calls"]),
+        ]
+    );
+}
+
+#[test]
+fn test_diagnostics_pin_toggle() {
+    let mut config = DiagnosticsConfig::default();
+    assert!(config.toggle_pinned("physics/step_time"));
+    assert!(config.toggle_pinned("fps"));
+    assert_eq!(config.pinned, ["physics/step_time", "fps"]);
+
+    assert!(!config.toggle_pinned("physics/step_time"));
+    assert_eq!(config.pinned, ["fps"]);
+}
+
+#[test]
+fn test_frame_spikes_are_captured_once_per_measurement() {
+    use bevy::diagnostic::{
+        Diagnostic, DiagnosticMeasurement, DiagnosticsStore, FrameTimeDiagnosticsPlugin,
+    };
+    use bevy::platform::time::Instant;
+    use msg_inspector::{capture_frame_spikes, DiagnosticsConfig, FrameSpikes};
+
+    fn measure(app: &mut App, value: f64) {
+        app.world_mut()
+            .resource_mut::<DiagnosticsStore>()
+            .get_mut(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+            .unwrap()
+            .add_measurement(DiagnosticMeasurement {
+                time: Instant::now(),
+                value,
+            });
+    }
+
+    let mut store = DiagnosticsStore::default();
+    store.add(Diagnostic::new(FrameTimeDiagnosticsPlugin::FRAME_TIME).with_suffix("ms"));
+    let mut app = App::new();
+    app.insert_resource(store)
+        .insert_resource(DiagnosticsConfig::default())
+        .init_resource::<FrameSpikes>()
+        .init_resource::<Time<bevy::time::Real>>()
+        .add_systems(Update, capture_frame_spikes);
+
+    measure(&mut app, 5.0);
+    app.update();
+    assert!(app.world().resource::<FrameSpikes>().is_empty());
+
+    measure(&mut app, 120.0);
+    app.update();
+    // No new measurement, so the same spike isn't recorded again
+    app.update();
+    let spikes = app.world().resource::<FrameSpikes>();
+    assert_eq!(spikes.len(), 1);
+    let spike = spikes.latest().unwrap();
+    assert_eq!(spike.frame_time_ms, 120.0);
+    assert_eq!(spike.diagnostics[0].path, "frame_time");
+    assert_eq!(spike.diagnostics[0].suffix, "ms");
+}
+
+#[test]
+fn test_entity_stats_group_by_archetype_and_component() {
+    use msg_inspector::EntityStats;
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    struct Health(#[allow(dead_code)] u32);
+
+    let mut world = World::new();
+    for _ in 0..3 {
+        world.spawn((Enemy, Health(10)));
+    }
+    world.spawn(Health(5));
+    // Inspector-owned entities are left out
+    world.spawn((Health(1), InspectorInternal));
+
+    let stats = EntityStats::collect(&world);
+    assert_eq!(stats.archetypes.len(), 2);
+    assert_eq!(stats.archetypes[0].entity_count, 3);
+    assert_eq!(stats.archetypes[0].bytes_per_entity, 4);
+
+    let health = stats.components.iter().find(|c| c.name == "Health").unwrap();
+    assert_eq!((health.entity_count, health.archetype_count), (4, 2));
+
+    let largest = EntityStats::archetype_entities(&world, stats.archetypes[0].id);
+    assert_eq!(largest.len(), 3);
+    assert!(largest.iter().all(|&e| world.entity(e).contains::<Enemy>()));
+}
+
+#[test]
+fn test_diagnostics_export_writes_csv_and_json() {
+    use bevy::diagnostic::{Diagnostic, DiagnosticMeasurement, DiagnosticPath, DiagnosticsStore};
+    use bevy::platform::time::Instant;
+    use msg_inspector::DiagnosticsExport;
+    use std::time::Duration;
+
+    let start = Instant::now();
+    let mut diagnostic = Diagnostic::new(DiagnosticPath::const_new("net/rtt")).with_suffix("ms");
+    for (i, value) in [20.0, 25.0].into_iter().enumerate() {
+        diagnostic.add_measurement(DiagnosticMeasurement {
+            time: start + Duration::from_secs(i as u64),
+            value,
+        });
+    }
+    let mut store = DiagnosticsStore::default();
+    store.add(diagnostic);
+    let mut world = World::new();
+    world.insert_resource(store);
+
+    let export = DiagnosticsExport::from_world(&world);
+    let mut csv = Vec::new();
+    export.write_csv(&mut csv).unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "path,time_secs,value,suffix\nnet/rtt,0,20,ms\nnet/rtt,1,25,ms\n"
+    );
+
+    let mut json = Vec::new();
+    export.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains("\"path\": \"net/rtt\""));
+    assert!(json.contains("\"spikes\": []"));
+}
+
+#[test]
+fn test_diagnostics_export_never_overwrites_files() {
+    use msg_inspector::{ExportFormat, export_diagnostics};
+
+    let dir = std::env::temp_dir().join("msg_inspector_export_unique");
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut world = World::new();
+    world.insert_resource(DiagnosticsConfig {
+        export_dir: dir.clone(),
+        ..default()
+    });
+
+    // Exports made within the same millisecond get distinct files
+    let paths: Vec<_> = (0..3)
+        .map(|_| export_diagnostics(&world, ExportFormat::Csv).unwrap())
+        .collect();
+    assert_ne!(paths[0], paths[1]);
+    assert_ne!(paths[1], paths[2]);
+    assert_ne!(paths[0], paths[2]);
+    assert!(paths.iter().all(|path| path.exists()));
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn test_system_timings_come_from_system_spans() {
+    use bevy::ecs::schedule::SystemSet;
+    use bevy::log::tracing::{self, info_span};
+    use bevy::log::tracing_subscriber::{layer::SubscriberExt, Registry};
+    use msg_inspector::{
+        collect_system_timings, locate_timed_systems, system_timing_layer, SystemTimings,
+    };
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct AiSet;
+
+    fn think() {}
+
+    let mut app = App::new();
+    app.init_resource::<SystemTimings>()
+        .add_systems(Update, think.in_set(AiSet))
+        .add_systems(Last, (collect_system_timings, locate_timed_systems).chain());
+    let layer = system_timing_layer(&mut app).unwrap();
+    let name = IntoSystem::into_system(think).name().to_string();
+
+    // The same span Bevy's `trace` feature wraps each system run in
+    tracing::subscriber::with_default(Registry::default().with(layer), || {
+        let span = info_span!(target: "bevy_ecs::system", parent: None, "system", name = name.as_str());
+        for _ in 0..2 {
+            let _guard = span.enter();
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        // Spans from outside Bevy's executor are ignored
+        let _guard = info_span!("system", name = "not_a_system").entered();
+    });
+    app.update();
+    app.update();
+
+    let timings = app.world().resource::<SystemTimings>();
+    assert_eq!(timings.len(), 1);
+    let think = timings.get(&name).unwrap();
+    // Both runs land in the first frame and the second frame has none
+    assert_eq!(think.history().len(), 2);
+    assert!(think.max_ms() >= 4.0);
+    assert_eq!(think.last_ms(), 0.0);
+    assert!((think.mean_ms() - think.max_ms() / 2.0).abs() < 1e-9);
+    assert_eq!(think.schedule.as_deref(), Some("Update"));
+    assert_eq!(think.sets, ["AiSet"]);
+}
+
+#[test]
+fn test_systems_in_last_are_located() {
+    use msg_inspector::{
+        SystemTimingSink, SystemTimings, collect_system_timings, locate_timed_systems,
+    };
+    use std::time::Duration;
+
+    fn cleanup() {}
+
+    let mut app = App::new();
+    app.init_resource::<SystemTimings>()
+        .init_resource::<SystemTimingSink>()
+        .add_systems(First, locate_timed_systems)
+        .add_systems(
+            Last,
+            (cleanup, collect_system_timings, locate_timed_systems).chain(),
+        );
+    app.update();
+
+    // `Last` is out of `Schedules` while it runs, so it is found in `First`
+    let name = IntoSystem::into_system(cleanup).name().to_string();
+    app.world()
+        .resource::<SystemTimingSink>()
+        .record(&name, Duration::from_millis(1));
+    app.update();
+    app.update();
+
+    let timings = app.world().resource::<SystemTimings>();
+    assert_eq!(
+        timings.get(&name).unwrap().schedule.as_deref(),
+        Some("Last")
+    );
+}
+
+#[test]
+fn test_schedule_graphs_capture_ordering_conditions_and_access() {
+    use bevy::ecs::schedule::SystemSet;
+    use msg_inspector::ScheduleGraphs;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct AiSet;
+
+    #[derive(Resource, Default)]
+    struct Difficulty;
+
+    #[derive(Component)]
+    struct Target;
+
+    fn think(_difficulty: Res<Difficulty>, _targets: Query<&mut Target>) {}
+    fn act() {}
+    fn enabled() -> bool {
+        true
+    }
+
+    let mut app = App::new();
+    app.init_resource::<Difficulty>()
+        .configure_sets(Update, AiSet.run_if(enabled))
+        .add_systems(Update, (think.in_set(AiSet), act.after(think)));
+    app.init_resource::<ScheduleGraphs>();
+    ScheduleGraphs::watch(&mut app.world_mut().resource_mut::<Schedules>());
+    app.update();
+
+    let think_name = IntoSystem::into_system(think).name().to_string();
+    let act_name = IntoSystem::into_system(act).name().to_string();
+    let graphs = app.world().resource::<ScheduleGraphs>();
+    let update = graphs.get("Update").unwrap();
+
+    let think = update.system(&think_name).unwrap();
+    assert_eq!(think.sets, ["AiSet"]);
+    assert_eq!(think.access.resource_reads, ["Difficulty"]);
+    assert_eq!(think.access.component_writes, ["Target"]);
+    assert!(think.access.component_reads.is_empty());
+
+    // `.after(think)` orders against the set Bevy makes for the `think` function
+    let act = update.system(&act_name).unwrap();
+    assert_eq!(act.after, [think_name]);
+    assert!(act.sets.is_empty());
+
+    let ai = update.set("AiSet").unwrap();
+    assert_eq!(ai.conditions.len(), 1);
+    assert!(ai.conditions[0].ends_with("enabled"));
+}
+
+#[test]
+fn test_schedules_added_after_startup_are_watched() {
+    use bevy::ecs::schedule::ScheduleLabel;
+    use msg_inspector::{ScheduleGraphs, watch_new_schedules};
+
+    #[derive(ScheduleLabel, Debug, Clone, PartialEq, Eq, Hash)]
+    struct LateSchedule;
+
+    fn late() {}
+
+    let mut app = App::new();
+    app.init_resource::<ScheduleGraphs>()
+        .add_systems(Last, watch_new_schedules);
+    ScheduleGraphs::watch(&mut app.world_mut().resource_mut::<Schedules>());
+    app.update();
+    assert!(
+        app.world()
+            .resource::<ScheduleGraphs>()
+            .get("LateSchedule")
+            .is_none()
+    );
+
+    app.add_systems(LateSchedule, late);
+    app.update();
+    app.world_mut().run_schedule(LateSchedule);
+
+    let late_name = IntoSystem::into_system(late).name().to_string();
+    let graphs = app.world().resource::<ScheduleGraphs>();
+    let schedule = graphs.get("LateSchedule").unwrap();
+    assert!(schedule.system(&late_name).is_some());
+}
+
+#[test]
+fn test_disabled_systems_are_skipped_until_enabled() {
+    use bevy::ecs::schedule::SystemSet;
+    use msg_inspector::{DisabledSystems, ScheduleGraphs};
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    struct PhysicsSet;
+
+    #[derive(Resource, Default)]
+    struct Runs {
+        ai: u32,
+        physics: u32,
+    }
+
+    fn ai(mut runs: ResMut<Runs>) {
+        runs.ai += 1;
+    }
+    fn physics(mut runs: ResMut<Runs>) {
+        runs.physics += 1;
+    }
+
+    let mut app = App::new();
+    app.init_resource::<Runs>()
+        .init_resource::<DisabledSystems>()
+        .add_systems(Update, (ai, physics.in_set(PhysicsSet)));
+    ScheduleGraphs::watch(&mut app.world_mut().resource_mut::<Schedules>());
+    let ai_name = IntoSystem::into_system(ai).name().to_string();
+
+    app.world_mut()
+        .resource_mut::<DisabledSystems>()
+        .disable(ai_name.clone());
+    app.update();
+    app.world_mut().resource_mut::<DisabledSystems>().toggle("PhysicsSet");
+    app.update();
+    app.world_mut().resource_mut::<DisabledSystems>().clear();
+    app.update();
+
+    let runs = app.world().resource::<Runs>();
+    assert_eq!((runs.ai, runs.physics), (1, 2));
+
+    // The injected condition is not listed as one of the system's own
+    let graphs = app.world().resource::<ScheduleGraphs>();
+    assert!(graphs.get("Update").unwrap().system(&ai_name).unwrap().conditions.is_empty());
+}
+
+#[test]
+fn test_time_control_steps_frames_and_fixed_ticks() {
+    use bevy::time::{TimeSystems, TimeUpdateStrategy};
+    use msg_inspector::{count_fixed_steps, step_time};
+    use std::time::Duration;
+
+    #[derive(Resource, Default)]
+    struct FixedRuns(u32);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(20)))
+        .insert_resource(Time::<Fixed>::from_hz(100.0))
+        .init_resource::<TimeControl>()
+        .init_resource::<FixedRuns>()
+        .add_systems(First, step_time.before(TimeSystems))
+        .add_systems(FixedLast, count_fixed_steps)
+        .add_systems(FixedUpdate, |mut runs: ResMut<FixedRuns>| runs.0 += 1);
+    app.update();
+    app.world_mut().resource_mut::<Time<Virtual>>().pause();
+    let elapsed = |app: &App| app.world().resource::<Time<Virtual>>().elapsed();
+
+    let start = elapsed(&app);
+    for _ in 0..3 {
+        app.update();
+    }
+    assert_eq!(elapsed(&app), start);
+
+    app.world_mut().resource_mut::<TimeControl>().step_frames(2);
+    for _ in 0..4 {
+        app.update();
+    }
+    assert_eq!(elapsed(&app), start + Duration::from_millis(40));
+    assert!(app.world().resource::<Time<Virtual>>().is_paused());
+    assert!(!app.world().resource::<TimeControl>().is_stepping());
+
+    // Each 20 ms frame holds two 10 ms ticks, so a three tick step stops halfway
+    let runs = app.world().resource::<FixedRuns>().0;
+    app.world_mut().resource_mut::<TimeControl>().step_fixed_ticks(3);
+    for _ in 0..4 {
+        app.update();
+    }
+    assert_eq!(app.world().resource::<FixedRuns>().0, runs + 3);
+    assert!(app.world().resource::<Time<Virtual>>().is_paused());
+}
+
+#[test]
+fn test_time_control_hotkeys_only_work_while_inspector_is_shown() {
+    use msg_inspector::time_control_hotkeys;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<ButtonInput<KeyCode>>()
+        .init_resource::<TimeControl>()
+        .insert_resource(InspectorEnabled(false))
+        .add_systems(Update, time_control_hotkeys);
+    let press = |app: &mut App, key| {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.reset_all();
+        keys.press(key);
+        app.update();
+    };
+    let paused = |app: &App| app.world().resource::<Time<Virtual>>().is_paused();
+
+    // The game keeps F9 while the inspector is hidden
+    press(&mut app, KeyCode::F9);
+    assert!(!paused(&app));
+
+    app.world_mut().resource_mut::<InspectorEnabled>().0 = true;
+    press(&mut app, KeyCode::F9);
+    assert!(paused(&app));
+
+    // A key set to `None` is turned off
+    app.world_mut().resource_mut::<TimeControl>().pause_key = None;
+    press(&mut app, KeyCode::F9);
+    assert!(paused(&app));
+}
+
+#[test]
+fn test_state_history_records_transitions_with_frames() {
+    use bevy::state::app::StatesPlugin;
+    use msg_inspector::{track_states, StateHistory};
+
+    #[derive(States, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    enum GameFlow {
+        #[default]
+        Menu,
+        Loading,
+        InGame,
+    }
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .init_state::<GameFlow>()
+        .register_type_mutable_state::<GameFlow>()
+        .init_resource::<StateHistory>()
+        .add_systems(Update, track_states);
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextState<GameFlow>>()
+        .set(GameFlow::Loading);
+    app.update();
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextState<GameFlow>>()
+        .set(GameFlow::InGame);
+    app.update();
+
+    let history = app.world().resource::<StateHistory>();
+    let state = history.get("GameFlow").unwrap();
+    assert_eq!(state.current.as_deref(), Some("InGame"));
+    assert!(state.can_set);
+    assert_eq!(state.variants, ["Menu", "Loading", "InGame"]);
+
+    let transitions: Vec<_> = state
+        .transitions()
+        .map(|t| (t.frame, t.from.as_deref(), t.to.as_deref()))
+        .collect();
+    assert_eq!(
+        transitions,
+        [
+            (0, None, Some("Menu")),
+            (1, Some("Menu"), Some("Loading")),
+            (3, Some("Loading"), Some("InGame")),
+        ]
+    );
+}
+
+#[test]
+fn test_message_monitor_counts_and_logs_payloads() {
+    use msg_inspector::{monitor_messages, MessageMonitor};
+
+    #[derive(Message, Reflect, Debug, Clone)]
+    struct Damage {
+        amount: u32,
+    }
+
+    #[derive(Message)]
+    struct Unreflected;
+
+    #[derive(Resource, Default)]
+    struct Hits(Vec<u32>);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_message::<Damage>()
+        .add_message::<Unreflected>()
+        .register_type::<Messages<Damage>>()
+        .init_resource::<Hits>()
+        .init_resource::<MessageMonitor>()
+        .add_systems(
+            Update,
+            |mut hits: ResMut<Hits>, mut damage: MessageWriter<Damage>| {
+                for amount in hits.0.drain(..) {
+                    damage.write(Damage { amount });
+                }
+            },
+        )
+        .add_systems(Last, monitor_messages);
+    app.update();
+    app.world_mut()
+        .resource_mut::<MessageMonitor>()
+        .set_logging("Damage", true);
+
+    app.world_mut().resource_mut::<Hits>().0 = vec![5, 7];
+    app.update();
+    app.update();
+    app.world_mut().resource_mut::<Hits>().0 = vec![11];
+    app.update();
+
+    let monitor = app.world().resource::<MessageMonitor>();
+    let damage = monitor.stream("Damage").unwrap();
+    assert!(damage.reflected);
+    assert_eq!(damage.total, 3);
+    assert_eq!(damage.counts().collect::<Vec<_>>(), [0, 2, 0, 1]);
+    assert!(!monitor.stream("Unreflected").unwrap().reflected);
+
+    let logged: Vec<_> = monitor.log().map(|m| (m.id, m.text.contains("amount: 7"))).collect();
+    assert_eq!(logged, [(0, false), (1, true), (2, false)]);
+}
+
+#[test]
+fn test_messages_still_reflect_the_fields_the_monitor_reads() {
+    use bevy::reflect::{ReflectRef, Struct};
+
+    #[derive(Message, Reflect, Debug)]
+    struct Ping;
+
+    // The Messages tab reads these private fields of `Messages<T>` by name. If
+    // a Bevy release renames one, the counts and the log silently go empty
+    fn field<'a>(value: &'a dyn Struct, name: &str) -> &'a dyn Struct {
+        match value.field(name).map(PartialReflect::reflect_ref) {
+            Some(ReflectRef::Struct(field)) => field,
+            _ => panic!("`{name}` is no longer a reflected struct field of `Messages<T>`"),
+        }
+    }
+
+    let mut messages = Messages::<Ping>::default();
+    messages.write(Ping);
+    let ReflectRef::Struct(messages) = messages.reflect_ref() else {
+        panic!("`Messages<T>` is no longer reflected as a struct");
+    };
+    assert!(
+        messages
+            .field("message_count")
+            .and_then(|count| count.try_downcast_ref::<usize>())
+            .is_some(),
+        "`Messages<T>::message_count` is gone"
+    );
+    for buffer in ["messages_a", "messages_b"] {
+        let sequence = field(messages, buffer);
+        assert!(
+            matches!(
+                sequence.field("messages").map(PartialReflect::reflect_ref),
+                Some(ReflectRef::List(_))
+            ),
+            "`Messages<T>::{buffer}.messages` is gone"
+        );
+    }
+    let sequence = field(messages, "messages_b");
+    let Some(ReflectRef::List(list)) = sequence.field("messages").map(PartialReflect::reflect_ref)
+    else {
+        unreachable!();
+    };
+    let instance = match list.get(0).map(PartialReflect::reflect_ref) {
+        Some(ReflectRef::Struct(instance)) => instance,
+        _ => panic!("the written message is not in `messages_b`"),
+    };
+    assert!(
+        instance.field("message").is_some(),
+        "`MessageInstance::message` is gone"
+    );
+    let id = field(instance, "message_id");
+    assert!(
+        id.field("id")
+            .and_then(|id| id.try_downcast_ref::<usize>())
+            .is_some(),
+        "`MessageId::id` is gone"
+    );
+}
+
+#[test]
+fn test_message_monitor_picks_up_streams_added_later() {
+    use msg_inspector::{MessageMonitor, monitor_messages};
+
+    #[derive(Message, Reflect, Debug)]
+    struct Spawned;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<MessageMonitor>()
+        .add_systems(Last, monitor_messages);
+    app.update();
+    assert!(
+        app.world()
+            .resource::<MessageMonitor>()
+            .stream("Spawned")
+            .is_none()
+    );
+
+    app.add_message::<Spawned>()
+        .register_type::<Messages<Spawned>>();
+    app.update();
+    app.world_mut().write_message(Spawned);
+    app.update();
+    let monitor = app.world().resource::<MessageMonitor>();
+    assert_eq!(monitor.stream("Spawned").unwrap().total, 1);
+}
+
+#[test]
+fn test_reflected_messages_are_sent_and_macros_round_trip() {
+    use msg_inspector::{load_macros, write_reflected_message, MessageSender};
+
+    #[derive(Message, Reflect, Default, Debug, Clone, PartialEq)]
+    struct Damage {
+        amount: u32,
+    }
+
+    #[derive(Resource, Default)]
+    struct Received(Vec<u32>);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_message::<Damage>()
+        .register_inspector_message::<Damage>()
+        .init_resource::<Received>()
+        .add_systems(
+            Update,
+            |mut received: ResMut<Received>, mut damage: MessageReader<Damage>| {
+                received.0.extend(damage.read().map(|d| d.amount));
+            },
+        );
+
+    assert!(write_reflected_message(app.world_mut(), &Damage { amount: 12 }));
+    assert!(!write_reflected_message(app.world_mut(), &5_u32));
+    app.update();
+    assert_eq!(app.world().resource::<Received>().0, [12]);
+
+    let path = std::env::temp_dir()
+        .join(format!("msg_inspector_macros_{}.ron", std::process::id()));
+    let mut sender = MessageSender::with_path(Some(path.clone()));
+    sender.add_macro("big hit", &Damage { amount: 99 });
+    sender.add_macro("small hit", &Damage { amount: 1 });
+    sender.add_macro("big hit", &Damage { amount: 100 });
+    assert_eq!(sender.macros().count(), 2);
+
+    let registry = app.world().resource::<AppTypeRegistry>().clone();
+    sender.save(&registry.read()).unwrap();
+    let loaded = load_macros(&path, &registry.read()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.len(), 2);
+    assert_eq!(loaded[0].name, "big hit");
+    assert_eq!(loaded[0].message_type(), "Damage");
+    assert_eq!(
+        loaded[0].message.try_downcast_ref::<Damage>(),
+        Some(&Damage { amount: 100 })
+    );
+
+    assert!(write_reflected_message(app.world_mut(), loaded[1].message.as_ref()));
+    app.update();
+    assert_eq!(app.world().resource::<Received>().0, [12, 1]);
+}
+
+#[test]
+fn test_observer_trace_records_targets_and_observers() {
+    use msg_inspector::ObserverTrace;
+
+    #[derive(EntityEvent)]
+    struct Explode {
+        entity: Entity,
+    }
+
+    #[derive(Event)]
+    struct RoundOver;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<ObserverTrace>()
+        .trace_inspector_entity_event::<Explode>()
+        .trace_inspector_event::<RoundOver>()
+        .add_observer(|_: On<RoundOver>| {});
+    let world = app.world_mut();
+    let barrel = world.spawn(Name::new("Barrel")).id();
+    let crate_entity = world.spawn_empty().id();
+    let watcher = world
+        .spawn((Name::new("Barrel fx"), Observer::new(|_: On<Explode>| {}).with_entity(barrel)))
+        .id();
+    app.update();
+
+    let world = app.world_mut();
+    world.trigger(Explode { entity: barrel });
+    world.trigger(Explode {
+        entity: crate_entity,
+    });
+    world.resource_mut::<ObserverTrace>().set_paused(true);
+    world.trigger(RoundOver);
+    world.resource_mut::<ObserverTrace>().set_paused(false);
+    app.update();
+    app.world_mut().trigger(RoundOver);
+
+    let trace = app.world().resource::<ObserverTrace>();
+    let records: Vec<_> = trace.records().collect();
+    assert_eq!(records.len(), 3);
+
+    assert_eq!(records[0].event, "Explode");
+    assert_eq!(records[0].target, Some(barrel));
+    assert_eq!(records[0].observers.len(), 1);
+    assert_eq!(records[0].observers[0].entity, watcher);
+    assert_eq!(records[0].observers[0].name, "Barrel fx");
+
+    assert_eq!(records[1].target, Some(crate_entity));
+    assert!(records[1].observers.is_empty());
+
+    assert_eq!(records[2].event, "RoundOver");
+    assert_eq!(records[2].target, None);
+    assert_eq!(records[2].observers.len(), 1);
+    assert!(records[2].frame > records[0].frame);
+}
+
+#[test]
+fn test_component_timeline_records_changes_of_watched_entities() {
+    use msg_inspector::{track_component_lifecycles, ComponentChange, ComponentTimeline};
+
+    #[derive(Component)]
+    struct Stunned;
+
+    // Reflected components are watched before their first insertion
+    #[derive(Component, Reflect)]
+    #[reflect(Component)]
+    struct Hit;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .register_type::<Hit>()
+        .init_resource::<ComponentTimeline>()
+        .add_systems(First, track_component_lifecycles);
+    let world = app.world_mut();
+    world.register_component::<Stunned>();
+    let player = world.spawn_empty().id();
+    let other = world.spawn_empty().id();
+    world.resource_mut::<ComponentTimeline>().watch([player]);
+    app.update();
+
+    let world = app.world_mut();
+    world.entity_mut(player).insert(Stunned);
+    world.entity_mut(other).insert(Stunned);
+    app.update();
+    let world = app.world_mut();
+    world.entity_mut(player).insert(Stunned);
+    // A marker that only exists within one frame
+    world.entity_mut(player).insert(Hit).remove::<Hit>();
+    app.update();
+    app.world_mut().entity_mut(player).despawn();
+
+    let timeline = app.world().resource::<ComponentTimeline>();
+    let changes: Vec<_> = timeline
+        .events()
+        .map(|event| (event.frame, event.name.as_str(), event.change))
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            (1, "Stunned", ComponentChange::Added),
+            (2, "Stunned", ComponentChange::Replaced),
+            (2, "Hit", ComponentChange::Added),
+            (2, "Hit", ComponentChange::Removed),
+            (3, "Stunned", ComponentChange::Removed),
+        ]
+    );
+    assert!(timeline.events().all(|event| event.entity == player));
+    assert_eq!(timeline.events_for(other).count(), 0);
+}
+
+#[test]
+fn test_lifecycle_observers_only_exist_while_entities_are_watched() {
+    use bevy::ecs::entity_disabling::Internal;
+    use msg_inspector::{ComponentTimeline, InspectorInternal, track_component_lifecycles};
+
+    // Observers are Bevy-internal entities, hidden from queries by default
+    fn lifecycle_observers(world: &mut World) -> usize {
+        world
+            .query_filtered::<(), (With<Observer>, With<InspectorInternal>, With<Internal>)>()
+            .iter(world)
+            .count()
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .init_resource::<ComponentTimeline>()
+        .add_systems(First, track_component_lifecycles);
+    app.update();
+    assert_eq!(lifecycle_observers(app.world_mut()), 0);
+
+    let player = app.world_mut().spawn_empty().id();
+    app.world_mut()
+        .resource_mut::<ComponentTimeline>()
+        .watch([player]);
+    app.update();
+    assert_eq!(lifecycle_observers(app.world_mut()), 3);
+
+    app.world_mut()
+        .resource_mut::<ComponentTimeline>()
+        .watch([]);
+    app.update();
+    assert_eq!(lifecycle_observers(app.world_mut()), 0);
+    app.update();
+    assert_eq!(lifecycle_observers(app.world_mut()), 0);
+}
+
+#[test]
+fn test_undo_history_restores_values_and_despawned_entities() {
+    use bevy::reflect::PartialReflect;
+    use msg_inspector::{
+        redo, undo, EditTarget, EntitySnapshot, InspectorEdit, UndoHistory, ValueChange,
+    };
+    use std::any::TypeId;
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .register_type::<Health>()
+        .register_type::<Name>()
+        .init_resource::<UndoHistory>();
+    let world = app.world_mut();
+    let player = world.spawn((Name::new("Player"), Health(100))).id();
+    let sword = world.spawn((Name::new("Sword"), ChildOf(player))).id();
+
+    // An edit that set the health from 100 to 5
+    world.entity_mut(player).insert(Health(5));
+    world.resource_mut::<UndoHistory>().push(
+        "Edit Health",
+        InspectorEdit::Values(vec![ValueChange {
+            target: EditTarget::Component {
+                entity: player,
+                component: TypeId::of::<Health>(),
+            },
+            name: "Health".into(),
+            before: Box::new(Health(100)).into_partial_reflect(),
+            after: Box::new(Health(5)).into_partial_reflect(),
+        }]),
+    );
+    assert!(undo(world));
+    assert_eq!(world.get::<Health>(player), Some(&Health(100)));
+    assert!(redo(world));
+    assert_eq!(world.get::<Health>(player), Some(&Health(5)));
+
+    // Despawning the player takes the sword with it
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let snapshot = EntitySnapshot::take(world, player, &registry.read());
+    assert_eq!(snapshot.children.len(), 1);
+    world.despawn(player);
+    world
+        .resource_mut::<UndoHistory>()
+        .push("Despawn Player", InspectorEdit::Despawn(snapshot));
+    assert!(world.get_entity(sword).is_err());
+
+    assert!(undo(world));
+    let mut players = world.query_filtered::<Entity, With<Health>>();
+    let respawned = players.single(world).unwrap();
+    assert_eq!(world.get::<Name>(respawned).unwrap().as_str(), "Player");
+    let children = world.get::<Children>(respawned).unwrap();
+    assert_eq!(children.len(), 1);
+    assert_eq!(world.get::<Name>(children[0]).unwrap().as_str(), "Sword");
+
+    // Earlier edits now refer to the respawned entity
+    assert!(undo(world));
+    assert_eq!(world.get::<Health>(respawned), Some(&Health(100)));
+    assert!(!undo(world));
+    assert!(redo(world));
+    assert!(redo(world));
+    assert!(world.get_entity(respawned).is_err());
+    assert!(!redo(world));
+    assert_eq!(world.resource::<UndoHistory>().undo_entries().len(), 2);
+}
+
+#[test]
+fn test_value_snapshots_merge_frames_of_one_gesture() {
+    use bevy::reflect::PartialReflect;
+    use msg_inspector::{InspectorEdit, UndoHistory, testing::ValueSnapshot};
+
+    #[derive(Component, Reflect, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    // One frame of the Inspector tab: snapshot, a widget writes, record
+    fn edit(world: &mut World, entity: Entity, gesture: u64, write: impl FnOnce(&mut World)) {
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let registry = registry.read();
+        let snapshot = ValueSnapshot::entities(world, &[entity], false, &registry);
+        let since = world.increment_change_tick();
+        write(world);
+        snapshot.record_changes(world, since, Some(gesture), &registry);
+    }
+
+    fn recorded_health(world: &World) -> Vec<(u32, u32)> {
+        let health = |value: &dyn PartialReflect| value.try_downcast_ref::<Health>().unwrap().0;
+        world
+            .resource::<UndoHistory>()
+            .undo_entries()
+            .map(|entry| match &entry.edit {
+                InspectorEdit::Values(changes) => {
+                    assert_eq!(changes.len(), 1);
+                    (
+                        health(changes[0].before.as_ref()),
+                        health(changes[0].after.as_ref()),
+                    )
+                }
+                _ => panic!("not a value edit"),
+            })
+            .collect()
+    }
+
+    let mut app = App::new();
+    app.register_type::<Health>().init_resource::<UndoHistory>();
+    let world = app.world_mut();
+    let player = world.spawn(Health(100)).id();
+    let set_health =
+        |health| move |world: &mut World| world.get_mut::<Health>(player).unwrap().0 = health;
+
+    // Frames of the same drag become one entry
+    edit(world, player, 1, set_health(90));
+    edit(world, player, 1, set_health(80));
+    assert_eq!(recorded_health(world), [(100, 80)]);
+
+    // A new drag starts a new entry
+    edit(world, player, 2, set_health(70));
+    assert_eq!(recorded_health(world), [(100, 80), (80, 70)]);
+
+    // Components the widgets did not mark changed are not compared
+    edit(world, player, 3, |world| {
+        world
+            .get_mut::<Health>(player)
+            .unwrap()
+            .bypass_change_detection()
+            .0 = 5;
+    });
+    assert_eq!(recorded_health(world), [(100, 80), (80, 70)]);
+}
+
+#[test]
+fn test_redo_despawn_keeps_snapshot_of_entity_the_game_despawned() {
+    use msg_inspector::{EntitySnapshot, InspectorEdit, UndoHistory, redo, undo};
+
+    #[derive(Component, Reflect, Clone, PartialEq, Debug)]
+    #[reflect(Component)]
+    struct Health(u32);
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .register_type::<Health>()
+        .init_resource::<UndoHistory>();
+    let world = app.world_mut();
+    let player = world.spawn(Health(100)).id();
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let snapshot = EntitySnapshot::take(world, player, &registry.read());
+    world.despawn(player);
+    world
+        .resource_mut::<UndoHistory>()
+        .push("Despawn Player", InspectorEdit::Despawn(snapshot));
+
+    assert!(undo(world));
+    let respawned = world
+        .query_filtered::<Entity, With<Health>>()
+        .single(world)
+        .unwrap();
+    // The game despawns the entity before the despawn is redone
+    world.despawn(respawned);
+    assert!(redo(world));
+    assert!(undo(world));
+    assert_eq!(
+        world.query::<&Health>().single(world).unwrap(),
+        &Health(100)
+    );
+}